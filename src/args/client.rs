@@ -1,41 +1,48 @@
 extern crate args;
 extern crate getopts;
 
-use std::{env, str::FromStr};
-
 use getopts::Occur;
 
 use args::Args;
 
-use crate::util::error::Error;
+use socha_2022_rust::Error;
+
+use super::{evaluate_argument, evaluate_opt_argument, reject_positional, CommandArgs};
 
 #[derive(Debug, Clone)]
 pub struct ClientArgs {
-    pub host: String,
-    pub port: i32,
+    pub host: Option<String>,
+    pub port: Option<i32>,
     pub reservation: Option<String>,
+    pub observe: Option<String>,
+    pub password: Option<String>,
+    pub prepare: bool,
+    pub launch: bool,
+    pub record_directory: Option<String>,
+    pub telemetry_path: Option<String>,
+    pub strategy: Option<String>,
 }
 
-impl ClientArgs {
+impl CommandArgs for ClientArgs {
     fn setup_args(program: &str) -> Args {
         let mut args = Args::new(
-            program,
-            "A client for the game Ostseeschach (Software Challenge 2022)",
+            &format!("{} play", program),
+            "Play a game on a server (default subcommand)",
         );
         args.option(
             "h",
             "host",
-            "The IP address of the host to connect to",
+            "The IP address of the host to connect to (default: localhost)",
             "HOST",
-            Occur::Req,
+            Occur::Optional,
             None,
         );
         args.option(
             "p",
             "port",
-            "The port used for the connection",
+            "The port used for the connection (default: 13050)",
             "PORT",
-            Occur::Req,
+            Occur::Optional,
             None,
         );
         args.option(
@@ -46,64 +53,89 @@ impl ClientArgs {
             Occur::Optional,
             None,
         );
+        args.option(
+            "o",
+            "observe",
+            "The id of a room to observe instead of joining a game.",
+            "ROOM",
+            Occur::Optional,
+            None,
+        );
+        args.option(
+            "w",
+            "password",
+            "The administrator password of the server, required for observing and preparing.",
+            "PASSWORD",
+            Occur::Optional,
+            None,
+        );
+        args.flag(
+            "",
+            "prepare",
+            "Prepare a game for two players and print the reservation codes.",
+        );
+        args.flag(
+            "",
+            "launch",
+            "Launch two local clients with the reservation codes of the prepared game.",
+        );
+        args.option(
+            "",
+            "record-dir",
+            "Record every game into a file named after the room id inside this directory.",
+            "DIR",
+            Occur::Optional,
+            None,
+        );
+        args.option(
+            "",
+            "telemetry",
+            "Append the search statistics of every calculated move to this JSON-lines file.",
+            "FILE",
+            Occur::Optional,
+            None,
+        );
+        args.option(
+            "s",
+            "strategy",
+            "The strategy used to calculate moves (default: random).",
+            "NAME",
+            Occur::Optional,
+            None,
+        );
 
         args
     }
 
-    fn evaluate_argument<T>(args: &Args, name: &str) -> Result<T, Error>
-    where
-        T: FromStr,
-    {
-        let args_value = args.value_of::<T>(name);
-        match args_value {
-            Ok(value) => Ok(value),
-            Err(error) => Err(Error::ArgsError(error)),
-        }
-    }
-
-    fn evaluate_opt_argument<T>(args: &Args, name: &str) -> Result<Option<T>, Error>
-    where
-        T: FromStr,
-    {
-        let args_value = args.optional_value_of::<T>(name);
-        match args_value {
-            Ok(value) => Ok(value),
-            Err(error) => Err(Error::ArgsError(error)),
-        }
-    }
-
-    fn create_client_args(args: &Args) -> Result<Self, Error> {
-        let host = Self::evaluate_argument::<String>(args, "host")?;
-        let port_string = Self::evaluate_argument::<String>(args, "port")?;
-        let reservation = Self::evaluate_opt_argument::<String>(args, "reservation")?;
-
-        let port_parse_result = port_string.parse::<i32>();
+    fn create(args: &Args, positional: Option<String>) -> Result<Self, Error> {
+        reject_positional(positional)?;
+
+        let host = evaluate_opt_argument::<String>(args, "host")?;
+        let port_string = evaluate_opt_argument::<String>(args, "port")?;
+        let reservation = evaluate_opt_argument::<String>(args, "reservation")?;
+        let observe = evaluate_opt_argument::<String>(args, "observe")?;
+        let password = evaluate_opt_argument::<String>(args, "password")?;
+        let prepare = evaluate_argument::<bool>(args, "prepare")?;
+        let launch = evaluate_argument::<bool>(args, "launch")?;
+        let record_directory = evaluate_opt_argument::<String>(args, "record-dir")?;
+        let telemetry_path = evaluate_opt_argument::<String>(args, "telemetry")?;
+        let strategy = evaluate_opt_argument::<String>(args, "strategy")?;
+
+        let port_parse_result = port_string.map(|port| port.parse::<i32>()).transpose();
         match port_parse_result {
             Ok(port) => Ok(Self {
                 host,
                 port,
                 reservation,
+                observe,
+                password,
+                prepare,
+                launch,
+                record_directory,
+                telemetry_path,
+                strategy,
             }),
             Err(error) => Err(Error::ParseIntError(error)),
         }
     }
-
-    pub fn collect() -> Result<Self, Error> {
-        let env_args: Vec<String> = env::args().collect();
-        let program = env_args.first().unwrap();
-
-        let mut args = Self::setup_args(program);
-
-        let parse_result = args.parse(env_args);
-
-        if parse_result.is_err() {
-            let usage = args.full_usage();
-            println!("{}", usage);
-
-            let error = parse_result.unwrap_err();
-            return Err(Error::ArgsError(error));
-        }
-
-        Self::create_client_args(&args)
-    }
 }
@@ -0,0 +1,165 @@
+use std::env;
+
+use socha_2022_rust::config::Config;
+use socha_2022_rust::Error;
+
+use super::client::ClientArgs;
+use super::global::GlobalArgs;
+use super::tools::{
+    AnalyzeArgs, BenchArgs, DiagramArgs, PerftArgs, ReplayArgs, SelfPlayArgs, SolveArgs,
+    TelemetryArgs, TuiArgs, ViewerArgs,
+};
+use super::CommandArgs;
+
+pub enum Command {
+    Play(ClientArgs),
+    Perft(PerftArgs),
+    Bench(BenchArgs),
+    Analyze(AnalyzeArgs),
+    Solve(SolveArgs),
+    SelfPlay(SelfPlayArgs),
+    Replay(ReplayArgs),
+    Telemetry(TelemetryArgs),
+    Diagram(DiagramArgs),
+    Viewer(ViewerArgs),
+    Tui(TuiArgs),
+    Help(String),
+}
+
+/// The subcommand together with the options shared by all subcommands.
+pub struct Invocation {
+    pub command: Command,
+    pub global_args: GlobalArgs,
+}
+
+const SUBCOMMANDS: [(&str, &str); 11] = [
+    ("play", "Play a game on a server (default)"),
+    ("perft", "Count the positions reachable from a position"),
+    ("bench", "Measure the speed of move generation and of a strategy"),
+    ("analyze", "Search a position and print the best moves"),
+    ("solve", "Prove a forced win within a number of plies"),
+    ("selfplay", "Play games between two strategies without a server"),
+    ("replay", "Step through a server replay or game recording"),
+    ("telemetry", "Summarize the search telemetry of played games"),
+    ("diagram", "Export a board diagram as SVG or PNG"),
+    ("viewer", "Write an HTML viewer for a server replay or game recording"),
+    ("tui", "Play against a strategy in the terminal"),
+];
+
+fn overview(program: &str) -> String {
+    let mut overview = format!(
+        "Usage: {} [SUBCOMMAND] [OPTIONS]\n\n\
+         A client for the game Ostseeschach (Software Challenge 2022)\n\n\
+         Subcommands:\n",
+        program
+    );
+    for (name, description) in SUBCOMMANDS.iter() {
+        overview.push_str(&format!("    {:<11}{}\n", name, description));
+    }
+    overview.push('\n');
+    overview.push_str(&GlobalArgs::usage());
+    overview.push_str(&format!(
+        "\n\nRun '{} <SUBCOMMAND> --help' for the options of a subcommand.",
+        program
+    ));
+    overview
+}
+
+fn collect_subcommand<T, F>(program: &str, raw_args: Vec<String>, wrap: F) -> Result<Command, Error>
+where
+    T: CommandArgs,
+    F: FnOnce(T) -> Command,
+{
+    match T::collect(program, raw_args)? {
+        Ok(args) => Ok(wrap(args)),
+        Err(usage) => Ok(Command::Help(usage)),
+    }
+}
+
+impl Invocation {
+    pub fn collect() -> Result<Self, Error> {
+        let mut env_args: Vec<String> = env::args().collect();
+        let program = env_args.remove(0);
+        let global_args = GlobalArgs::extract(&mut env_args)?;
+        let command = Command::collect(&program, env_args)?;
+
+        Ok(Self {
+            command,
+            global_args,
+        })
+    }
+}
+
+impl Command {
+    fn collect(program: &str, mut env_args: Vec<String>) -> Result<Self, Error> {
+        let subcommand = match env_args.first() {
+            Some(first) if !first.starts_with('-') => env_args.remove(0),
+            _ => String::from("play"),
+        };
+
+        match subcommand.as_str() {
+            "play" => collect_subcommand(program, env_args, Command::Play),
+            "perft" => collect_subcommand(program, env_args, Command::Perft),
+            "bench" => collect_subcommand(program, env_args, Command::Bench),
+            "analyze" => collect_subcommand(program, env_args, Command::Analyze),
+            "solve" => collect_subcommand(program, env_args, Command::Solve),
+            "selfplay" => collect_subcommand(program, env_args, Command::SelfPlay),
+            "replay" => collect_subcommand(program, env_args, Command::Replay),
+            "telemetry" => collect_subcommand(program, env_args, Command::Telemetry),
+            "diagram" => collect_subcommand(program, env_args, Command::Diagram),
+            "viewer" => collect_subcommand(program, env_args, Command::Viewer),
+            "tui" => collect_subcommand(program, env_args, Command::Tui),
+            "help" => Ok(Command::Help(overview(program))),
+            _ => {
+                println!("{}", overview(program));
+                Err(Error::SimpleError(format!(
+                    "Unknown subcommand '{}'",
+                    subcommand
+                )))
+            }
+        }
+    }
+}
+
+impl Command {
+    /// Overrides the values of the configuration with the ones given on the
+    /// command line.
+    pub fn apply_to_config(&self, config: &mut Config) {
+        let strategy = match self {
+            Command::Play(client_args) => {
+                if let Some(host) = &client_args.host {
+                    config.host = host.clone();
+                }
+                if let Some(port) = client_args.port {
+                    config.port = port;
+                }
+                if let Some(record_directory) = &client_args.record_directory {
+                    config.record_directory = Some(record_directory.clone());
+                }
+                if let Some(telemetry_path) = &client_args.telemetry_path {
+                    config.telemetry_path = Some(telemetry_path.clone());
+                }
+                &client_args.strategy
+            }
+            Command::Bench(bench_args) => &bench_args.strategy,
+            Command::Replay(replay_args) => &replay_args.strategy,
+            Command::Viewer(viewer_args) => &viewer_args.strategy,
+            Command::Tui(tui_args) => {
+                // Messages on stderr would end up in the middle of the screen
+                config.logging.duplicate_to_stderr = String::from("none");
+                &tui_args.strategy
+            }
+            Command::Perft(_)
+            | Command::SelfPlay(_)
+            | Command::Telemetry(_)
+            | Command::Diagram(_)
+            | Command::Analyze(_)
+            | Command::Solve(_)
+            | Command::Help(_) => &None,
+        };
+
+        if let Some(strategy) = strategy {
+            config.strategy = strategy.clone();
+        }
+    }
+}
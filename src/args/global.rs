@@ -0,0 +1,175 @@
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use socha_2022_rust::config::{Config, DEFAULT_CONFIG_PATH};
+use socha_2022_rust::Error;
+
+/// The options shared by all subcommands. They come before the subcommand
+/// name and are removed before the subcommand is parsed.
+#[derive(Debug, Clone, Default)]
+pub struct GlobalArgs {
+    pub config_path: Option<String>,
+    pub log_level: Option<String>,
+    pub log_directory: Option<String>,
+    pub log_stderr: Option<String>,
+    pub log_rotate_size_kb: Option<u64>,
+    pub no_log_file: bool,
+    pub board_style: Option<String>,
+    pub time_budget_ms: Option<u64>,
+    pub hash_size_mb: Option<usize>,
+    /// The global options as they were given, to pass them on to launched
+    /// clients.
+    pub raw_args: Vec<String>,
+}
+
+pub const GLOBAL_USAGE: [(&str, &str); 9] = [
+    ("-c, --config FILE", "The configuration file"),
+    ("--log-level LEVEL", "The log level, e.g. info or debug"),
+    ("--log-dir DIR", "The directory log files are written to"),
+    (
+        "--log-stderr LEVEL",
+        "The level up to which messages are duplicated to stderr (none to disable)",
+    ),
+    ("--log-rotate-kb SIZE", "Rotate log files when they exceed this size"),
+    (
+        "--no-log-file",
+        "Don't write a log file, log to stderr only (up to the --log-stderr level)",
+    ),
+    (
+        "--board-style STYLE",
+        "How boards are printed: auto, color, unicode or ascii",
+    ),
+    ("--time-budget-ms MS", "The time the server grants for every move"),
+    ("--hash-mb SIZE", "The memory of the engine's tables"),
+];
+
+const GLOBAL_OPTIONS: [&str; 9] = [
+    "-c",
+    "--config",
+    "--log-level",
+    "--log-dir",
+    "--log-stderr",
+    "--log-rotate-kb",
+    "--board-style",
+    "--time-budget-ms",
+    "--hash-mb",
+];
+
+const GLOBAL_FLAGS: [&str; 1] = ["--no-log-file"];
+
+fn extract_option(raw_args: &mut Vec<String>, names: &[&str]) -> Result<Option<String>, Error> {
+    let position = raw_args
+        .iter()
+        .position(|arg| names.contains(&arg.as_str()));
+    match position {
+        Some(position) => {
+            raw_args.remove(position);
+            if position < raw_args.len() {
+                Ok(Some(raw_args.remove(position)))
+            } else {
+                Err(Error::SimpleError(format!(
+                    "Missing value for option {}",
+                    names.join("/")
+                )))
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+fn extract_number<T>(raw_args: &mut Vec<String>, name: &str) -> Result<Option<T>, Error>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    extract_option(raw_args, &[name])?
+        .map(|value| value.parse::<T>())
+        .transpose()
+        .map_err(Error::ParseIntError)
+}
+
+fn extract_flag(raw_args: &mut Vec<String>, name: &str) -> bool {
+    let length = raw_args.len();
+    raw_args.retain(|arg| arg != name);
+    raw_args.len() != length
+}
+
+/// The number of leading arguments that are global options or their values.
+fn global_prefix_length(raw_args: &[String]) -> usize {
+    let mut length = 0;
+    while let Some(arg) = raw_args.get(length) {
+        if GLOBAL_FLAGS.contains(&arg.as_str()) {
+            length += 1;
+        } else if GLOBAL_OPTIONS.contains(&arg.as_str()) {
+            length += 2;
+        } else {
+            break;
+        }
+    }
+    length.min(raw_args.len())
+}
+
+impl GlobalArgs {
+    /// Removes the global options in front of the subcommand from the
+    /// arguments. Everything from the subcommand name on is left to the
+    /// subcommand, so its options and positionals are never taken.
+    pub fn extract(raw_args: &mut Vec<String>) -> Result<Self, Error> {
+        let prefix_length = global_prefix_length(raw_args);
+        let mut prefix = raw_args.drain(..prefix_length).collect::<Vec<_>>();
+        let raw_args = prefix.clone();
+
+        Ok(Self {
+            config_path: extract_option(&mut prefix, &["-c", "--config"])?,
+            log_level: extract_option(&mut prefix, &["--log-level"])?,
+            log_directory: extract_option(&mut prefix, &["--log-dir"])?,
+            log_stderr: extract_option(&mut prefix, &["--log-stderr"])?,
+            log_rotate_size_kb: extract_number(&mut prefix, "--log-rotate-kb")?,
+            no_log_file: extract_flag(&mut prefix, "--no-log-file"),
+            board_style: extract_option(&mut prefix, &["--board-style"])?,
+            time_budget_ms: extract_number(&mut prefix, "--time-budget-ms")?,
+            hash_size_mb: extract_number(&mut prefix, "--hash-mb")?,
+            raw_args,
+        })
+    }
+
+    pub fn usage() -> String {
+        let mut usage = String::from("Global options:\n");
+        for (option, description) in GLOBAL_USAGE.iter() {
+            usage.push_str(&format!("    {:<24}{}\n", option, description));
+        }
+        usage.push_str("\nGlobal options come before the subcommand.");
+        usage.push_str(&format!(
+            "\nThe configuration file defaults to {} if it exists.",
+            DEFAULT_CONFIG_PATH
+        ));
+        usage
+    }
+
+    /// Overrides the values of the configuration with the ones given on the
+    /// command line.
+    pub fn apply_to_config(&self, config: &mut Config) {
+        if let Some(log_level) = &self.log_level {
+            config.logging.level = log_level.clone();
+        }
+        if let Some(log_directory) = &self.log_directory {
+            config.logging.directory = log_directory.clone();
+        }
+        if let Some(log_stderr) = &self.log_stderr {
+            config.logging.duplicate_to_stderr = log_stderr.clone();
+        }
+        if let Some(log_rotate_size_kb) = self.log_rotate_size_kb {
+            config.logging.rotate_size_kb = Some(log_rotate_size_kb);
+        }
+        if self.no_log_file {
+            config.logging.to_file = false;
+        }
+        if let Some(board_style) = &self.board_style {
+            config.board_style = board_style.clone();
+        }
+        if let Some(time_budget_ms) = self.time_budget_ms {
+            config.engine.time_budget_ms = time_budget_ms;
+        }
+        if let Some(hash_size_mb) = self.hash_size_mb {
+            config.engine.hash_size_mb = hash_size_mb;
+        }
+    }
+}
@@ -1 +1,108 @@
+extern crate args;
+extern crate getopts;
+
 pub mod client;
+pub mod command;
+pub mod global;
+pub mod tools;
+
+use std::str::FromStr;
+
+use args::Args;
+
+use socha_2022_rust::Error;
+
+/// The arguments of a single subcommand.
+pub trait CommandArgs: Sized {
+    fn setup_args(program: &str) -> Args;
+
+    /// Creates the arguments from the parsed options and the positional
+    /// argument, if there is one.
+    fn create(args: &Args, positional: Option<String>) -> Result<Self, Error>;
+
+    /// Parses the given raw arguments. Returns the usage text instead if
+    /// `--help` was passed.
+    fn collect(program: &str, raw_args: Vec<String>) -> Result<Result<Self, String>, Error> {
+        let mut args = Self::setup_args(program);
+        args.flag("", "help", "Print this help text.");
+
+        if let Err(error) = args.parse(&raw_args) {
+            let usage = args.full_usage();
+            println!("{}", usage);
+
+            return Err(Error::ArgsError(error));
+        }
+
+        if evaluate_argument::<bool>(&args, "help")? {
+            return Ok(Err(args.full_usage()));
+        }
+
+        let mut free_args = free_arguments::<Self>(program, raw_args).into_iter();
+        let positional = free_args.next();
+        reject_positional(free_args.next())?;
+
+        Ok(Ok(Self::create(&args, positional)?))
+    }
+}
+
+/// Whether the option takes the following argument as its value. The args
+/// crate doesn't expose which options take values, so the option is parsed
+/// on its own: that only fails if its value is missing.
+fn takes_value<T: CommandArgs>(program: &str, option: &str) -> bool {
+    let mut args = T::setup_args(program);
+    args.flag("", "help", "Print this help text.");
+    args.parse(&[option]).is_err()
+}
+
+/// The arguments that are neither options nor their values, in the order
+/// they were given. The args crate drops these after parsing.
+fn free_arguments<T: CommandArgs>(program: &str, raw_args: Vec<String>) -> Vec<String> {
+    let mut free_args = Vec::new();
+    let mut raw_args = raw_args.into_iter();
+    while let Some(arg) = raw_args.next() {
+        if arg == "--" {
+            free_args.extend(raw_args);
+            break;
+        } else if arg.starts_with('-') && arg.len() > 1 {
+            if takes_value::<T>(program, &arg) {
+                raw_args.next();
+            }
+        } else {
+            free_args.push(arg);
+        }
+    }
+    free_args
+}
+
+/// Fails if a subcommand that takes no positional argument got one.
+pub fn reject_positional(positional: Option<String>) -> Result<(), Error> {
+    match positional {
+        Some(positional) => Err(Error::SimpleError(format!(
+            "Unexpected argument '{}'",
+            positional
+        ))),
+        None => Ok(()),
+    }
+}
+
+pub fn evaluate_argument<T>(args: &Args, name: &str) -> Result<T, Error>
+where
+    T: FromStr,
+{
+    let args_value = args.value_of::<T>(name);
+    match args_value {
+        Ok(value) => Ok(value),
+        Err(error) => Err(Error::ArgsError(error)),
+    }
+}
+
+pub fn evaluate_opt_argument<T>(args: &Args, name: &str) -> Result<Option<T>, Error>
+where
+    T: FromStr,
+{
+    let args_value = args.optional_value_of::<T>(name);
+    match args_value {
+        Ok(value) => Ok(value),
+        Err(error) => Err(Error::ArgsError(error)),
+    }
+}
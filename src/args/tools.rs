@@ -0,0 +1,494 @@
+extern crate args;
+extern crate getopts;
+
+use getopts::Occur;
+
+use args::Args;
+
+use socha_2022_rust::game::notation::START_POSITION;
+use socha_2022_rust::Error;
+use socha_2022_rust::PlayerTeam;
+
+use super::{evaluate_argument, evaluate_opt_argument, reject_positional, CommandArgs};
+
+fn strategy_option(args: &mut Args, long_name: &str, description: &str) {
+    args.option(
+        "",
+        long_name,
+        &format!("{} (default: the configured strategy)", description),
+        "NAME",
+        Occur::Optional,
+        None,
+    );
+}
+
+/// Options for taking a position from a server replay or game recording
+/// instead of the position argument.
+fn replay_options(args: &mut Args) {
+    args.option(
+        "",
+        "replay",
+        "Take the position from a server replay or game recording instead.",
+        "FILE",
+        Occur::Optional,
+        None,
+    );
+    args.option(
+        "t",
+        "turn",
+        "The turn of the replay to use (default: the last one).",
+        "TURN",
+        Occur::Optional,
+        None,
+    );
+}
+
+fn required_positional(positional: Option<String>, name: &str) -> Result<String, Error> {
+    positional.ok_or_else(|| Error::SimpleError(format!("Missing argument <{}>", name)))
+}
+
+#[derive(Debug, Clone)]
+pub struct PerftArgs {
+    pub position: String,
+    pub depth: u32,
+}
+
+impl CommandArgs for PerftArgs {
+    fn setup_args(program: &str) -> Args {
+        let mut args = Args::new(
+            &format!("{} perft [POSITION]", program),
+            "Count the positions reachable from a position (the start position by default)",
+        );
+        args.option(
+            "d",
+            "depth",
+            "The number of plies to search.",
+            "DEPTH",
+            Occur::Optional,
+            Some(String::from("4")),
+        );
+
+        args
+    }
+
+    fn create(args: &Args, positional: Option<String>) -> Result<Self, Error> {
+        Ok(Self {
+            position: positional.unwrap_or_else(|| String::from(START_POSITION)),
+            depth: evaluate_argument::<u32>(args, "depth")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchArgs {
+    pub depth: u32,
+    pub strategy: Option<String>,
+}
+
+impl CommandArgs for BenchArgs {
+    fn setup_args(program: &str) -> Args {
+        let mut args = Args::new(
+            &format!("{} bench", program),
+            "Measure the speed of move generation and of a strategy",
+        );
+        args.option(
+            "d",
+            "depth",
+            "The perft depth used for measuring move generation.",
+            "DEPTH",
+            Occur::Optional,
+            Some(String::from("3")),
+        );
+        strategy_option(&mut args, "strategy", "The strategy to measure");
+
+        args
+    }
+
+    fn create(args: &Args, positional: Option<String>) -> Result<Self, Error> {
+        reject_positional(positional)?;
+        Ok(Self {
+            depth: evaluate_argument::<u32>(args, "depth")?,
+            strategy: evaluate_opt_argument::<String>(args, "strategy")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AnalyzeArgs {
+    pub position: Option<String>,
+    pub replay: Option<String>,
+    pub turn: Option<u32>,
+    pub depth: Option<u32>,
+    pub time_ms: Option<u64>,
+    pub multi_pv: usize,
+}
+
+impl CommandArgs for AnalyzeArgs {
+    fn setup_args(program: &str) -> Args {
+        let mut args = Args::new(
+            &format!("{} analyze [POSITION]", program),
+            "Search a position and print the best moves for every depth",
+        );
+        replay_options(&mut args);
+        args.option(
+            "d",
+            "depth",
+            "The depth to search to (default: no limit when a time is given).",
+            "DEPTH",
+            Occur::Optional,
+            None,
+        );
+        args.option(
+            "",
+            "time-ms",
+            "The time to search for (default: the configured move time when no depth is given).",
+            "MS",
+            Occur::Optional,
+            None,
+        );
+        args.option(
+            "m",
+            "multi-pv",
+            "The number of best moves to print.",
+            "N",
+            Occur::Optional,
+            Some(String::from("3")),
+        );
+
+        args
+    }
+
+    fn create(args: &Args, positional: Option<String>) -> Result<Self, Error> {
+        let replay = evaluate_opt_argument::<String>(args, "replay")?;
+        if positional.is_none() && replay.is_none() {
+            return Err(Error::SimpleError(String::from(
+                "Missing argument <POSITION> or option --replay",
+            )));
+        }
+
+        let multi_pv = evaluate_argument::<usize>(args, "multi-pv")?;
+        if multi_pv == 0 {
+            return Err(Error::SimpleError(String::from(
+                "The option --multi-pv needs at least one move",
+            )));
+        }
+
+        Ok(Self {
+            position: positional,
+            replay,
+            turn: evaluate_opt_argument::<u32>(args, "turn")?,
+            depth: evaluate_opt_argument::<u32>(args, "depth")?,
+            time_ms: evaluate_opt_argument::<u64>(args, "time-ms")?,
+            multi_pv,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SolveArgs {
+    pub position: Option<String>,
+    pub replay: Option<String>,
+    pub turn: Option<u32>,
+    pub plies: u32,
+    pub time_ms: Option<u64>,
+    pub proof: bool,
+}
+
+impl CommandArgs for SolveArgs {
+    fn setup_args(program: &str) -> Args {
+        let mut args = Args::new(
+            &format!("{} solve [POSITION]", program),
+            "Prove a forced win of the team to move within a number of plies",
+        );
+        replay_options(&mut args);
+        args.option(
+            "p",
+            "plies",
+            "The number of plies the win has to be reached in.",
+            "PLIES",
+            Occur::Optional,
+            Some(String::from("5")),
+        );
+        args.option(
+            "",
+            "time-ms",
+            "The time to search for (default: no limit).",
+            "MS",
+            Occur::Optional,
+            None,
+        );
+        args.flag(
+            "",
+            "proof",
+            "Prove the result with perfect play by a proof-number search, without a ply limit.",
+        );
+
+        args
+    }
+
+    fn create(args: &Args, positional: Option<String>) -> Result<Self, Error> {
+        let replay = evaluate_opt_argument::<String>(args, "replay")?;
+        if positional.is_none() && replay.is_none() {
+            return Err(Error::SimpleError(String::from(
+                "Missing argument <POSITION> or option --replay",
+            )));
+        }
+
+        Ok(Self {
+            position: positional,
+            replay,
+            turn: evaluate_opt_argument::<u32>(args, "turn")?,
+            plies: evaluate_argument::<u32>(args, "plies")?,
+            time_ms: evaluate_opt_argument::<u64>(args, "time-ms")?,
+            proof: evaluate_argument::<bool>(args, "proof")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SelfPlayArgs {
+    pub games: u32,
+    pub strategy_one: Option<String>,
+    pub strategy_two: Option<String>,
+    pub position: Option<String>,
+}
+
+impl CommandArgs for SelfPlayArgs {
+    fn setup_args(program: &str) -> Args {
+        let mut args = Args::new(
+            &format!("{} selfplay", program),
+            "Play games between two strategies without a server",
+        );
+        args.option(
+            "g",
+            "games",
+            "The number of games to play.",
+            "GAMES",
+            Occur::Optional,
+            Some(String::from("10")),
+        );
+        strategy_option(&mut args, "strategy-one", "The strategy of team one");
+        strategy_option(&mut args, "strategy-two", "The strategy of team two");
+        args.option(
+            "",
+            "position",
+            "The position every game starts from (a random start position by default).",
+            "POSITION",
+            Occur::Optional,
+            None,
+        );
+
+        args
+    }
+
+    fn create(args: &Args, positional: Option<String>) -> Result<Self, Error> {
+        reject_positional(positional)?;
+        Ok(Self {
+            games: evaluate_argument::<u32>(args, "games")?,
+            strategy_one: evaluate_opt_argument::<String>(args, "strategy-one")?,
+            strategy_two: evaluate_opt_argument::<String>(args, "strategy-two")?,
+            position: evaluate_opt_argument::<String>(args, "position")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplayArgs {
+    pub file: String,
+    pub strategy: Option<String>,
+    pub check: bool,
+}
+
+impl CommandArgs for ReplayArgs {
+    fn setup_args(program: &str) -> Args {
+        let mut args = Args::new(
+            &format!("{} replay <FILE>", program),
+            "Step through a server replay or game recording",
+        );
+        strategy_option(
+            &mut args,
+            "strategy",
+            "The strategy the played moves are compared against",
+        );
+        args.flag(
+            "",
+            "check",
+            "Only check every game state against the one predicted by our rules.",
+        );
+
+        args
+    }
+
+    fn create(args: &Args, positional: Option<String>) -> Result<Self, Error> {
+        Ok(Self {
+            file: required_positional(positional, "FILE")?,
+            strategy: evaluate_opt_argument::<String>(args, "strategy")?,
+            check: evaluate_argument::<bool>(args, "check")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TelemetryArgs {
+    pub file: String,
+}
+
+impl CommandArgs for TelemetryArgs {
+    fn setup_args(program: &str) -> Args {
+        Args::new(
+            &format!("{} telemetry <FILE>", program),
+            "Summarize the search telemetry written with 'play --telemetry FILE'",
+        )
+    }
+
+    fn create(_args: &Args, positional: Option<String>) -> Result<Self, Error> {
+        Ok(Self {
+            file: required_positional(positional, "FILE")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DiagramArgs {
+    pub position: String,
+    pub replay: Option<String>,
+    pub turn: Option<u32>,
+    pub output: String,
+    pub candidates: Option<String>,
+    pub size: u32,
+}
+
+impl CommandArgs for DiagramArgs {
+    fn setup_args(program: &str) -> Args {
+        let mut args = Args::new(
+            &format!("{} diagram [POSITION]", program),
+            "Export a board diagram as SVG or PNG (the start position by default)",
+        );
+        args.option(
+            "o",
+            "output",
+            "The output file, written as PNG if it ends with .png.",
+            "FILE",
+            Occur::Optional,
+            Some(String::from("diagram.svg")),
+        );
+        replay_options(&mut args);
+        args.option(
+            "",
+            "candidates",
+            "Comma-separated moves drawn as arrows, e.g. a1-b2,c3-d4.",
+            "MOVES",
+            Occur::Optional,
+            None,
+        );
+        args.option(
+            "",
+            "size",
+            "The edge length of a square in pixels.",
+            "PIXELS",
+            Occur::Optional,
+            Some(String::from("64")),
+        );
+
+        args
+    }
+
+    fn create(args: &Args, positional: Option<String>) -> Result<Self, Error> {
+        Ok(Self {
+            position: positional.unwrap_or_else(|| String::from(START_POSITION)),
+            replay: evaluate_opt_argument::<String>(args, "replay")?,
+            turn: evaluate_opt_argument::<u32>(args, "turn")?,
+            output: evaluate_argument::<String>(args, "output")?,
+            candidates: evaluate_opt_argument::<String>(args, "candidates")?,
+            size: evaluate_argument::<u32>(args, "size")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ViewerArgs {
+    pub file: String,
+    pub output: String,
+    pub analyze: bool,
+    pub strategy: Option<String>,
+}
+
+impl CommandArgs for ViewerArgs {
+    fn setup_args(program: &str) -> Args {
+        let mut args = Args::new(
+            &format!("{} viewer <FILE>", program),
+            "Write a self-contained HTML viewer for a server replay or game recording",
+        );
+        args.option(
+            "o",
+            "output",
+            "The HTML file to write.",
+            "FILE",
+            Occur::Optional,
+            Some(String::from("replay.html")),
+        );
+        args.flag(
+            "",
+            "analyze",
+            "Evaluate turns without recorded search information with the strategy.",
+        );
+        strategy_option(&mut args, "strategy", "The strategy used for --analyze");
+
+        args
+    }
+
+    fn create(args: &Args, positional: Option<String>) -> Result<Self, Error> {
+        Ok(Self {
+            file: required_positional(positional, "FILE")?,
+            output: evaluate_argument::<String>(args, "output")?,
+            analyze: evaluate_argument::<bool>(args, "analyze")?,
+            strategy: evaluate_opt_argument::<String>(args, "strategy")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TuiArgs {
+    pub position: Option<String>,
+    pub team: PlayerTeam,
+    pub strategy: Option<String>,
+}
+
+impl CommandArgs for TuiArgs {
+    fn setup_args(program: &str) -> Args {
+        let mut args = Args::new(
+            &format!("{} tui [POSITION]", program),
+            "Play against a strategy in the terminal (a random start position by default)",
+        );
+        args.option(
+            "t",
+            "team",
+            "The team you play: one or two.",
+            "TEAM",
+            Occur::Optional,
+            Some(String::from("one")),
+        );
+        strategy_option(&mut args, "strategy", "The strategy to play against");
+
+        args
+    }
+
+    fn create(args: &Args, positional: Option<String>) -> Result<Self, Error> {
+        let team = match evaluate_argument::<String>(args, "team")?.to_lowercase().as_str() {
+            "one" | "1" => PlayerTeam::One,
+            "two" | "2" => PlayerTeam::Two,
+            team => {
+                return Err(Error::SimpleError(format!(
+                    "Unknown team '{}', expected one or two",
+                    team
+                )))
+            }
+        };
+
+        Ok(Self {
+            position: positional,
+            team,
+            strategy: evaluate_opt_argument::<String>(args, "strategy")?,
+        })
+    }
+}
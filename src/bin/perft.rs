@@ -0,0 +1,32 @@
+//! Counts the positions reachable from a position. Same as
+//! `socha_2022_rust perft POSITION --depth DEPTH`.
+
+use std::env;
+use std::time::Instant;
+
+use socha_2022_rust::game::{notation::parse_position, perft::perft};
+use socha_2022_rust::Error;
+
+fn main() -> Result<(), Error> {
+    let usage = || Error::SimpleError(String::from("Usage: perft <POSITION> <DEPTH>"));
+    let position = env::args().nth(1).ok_or_else(usage)?;
+    let depth = env::args()
+        .nth(2)
+        .ok_or_else(usage)?
+        .parse::<u32>()
+        .map_err(Error::ParseIntError)?;
+
+    let game_state = parse_position(&position)?;
+    for depth in 1..=depth {
+        let start_time = Instant::now();
+        let nodes = perft(&game_state, depth);
+        println!(
+            "Depth {}: {} nodes in {:?}",
+            depth,
+            nodes,
+            start_time.elapsed()
+        );
+    }
+
+    Ok(())
+}
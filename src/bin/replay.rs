@@ -0,0 +1,36 @@
+//! Steps through a server replay or game recording with the settings of the
+//! default configuration file. Same as `socha_2022_rust replay FILE`.
+
+extern crate log;
+
+use std::env;
+
+use socha_2022_rust::game::{render::BoardRenderer, render::RenderStyle, replay::load_replay};
+use socha_2022_rust::logic::{recording::load_recording, replay, strategy::strategy_from_name};
+use socha_2022_rust::{config::Config, setup_logger, Error};
+
+fn main() -> Result<(), Error> {
+    let config = Config::load_or_default(None)?;
+    setup_logger(&config.logging)?;
+
+    let replay_path = env::args()
+        .nth(1)
+        .ok_or_else(|| Error::SimpleError(String::from("Usage: replay <FILE>")))?;
+    let game_states = load_replay(&replay_path)?;
+    log::info!(
+        "Loaded {} game states from {}",
+        game_states.len(),
+        replay_path
+    );
+
+    let recording = load_recording(&replay_path).ok();
+    let mut strategy = strategy_from_name(&config.strategy, &config.engine)?;
+    let renderer = BoardRenderer::new(RenderStyle::from_name(&config.board_style)?);
+    replay::step_through_replay(
+        &game_states,
+        recording.as_ref(),
+        strategy.as_mut(),
+        &renderer,
+    );
+    Ok(())
+}
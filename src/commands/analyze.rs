@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use socha_2022_rust::game::{
+    game_state::GameState,
+    notation::{format_annotated_move, format_move_in, format_variation},
+};
+use socha_2022_rust::logic::search::{Search, SearchLimits, SearchReport};
+use socha_2022_rust::{config::Config, Error};
+
+use crate::args::tools::AnalyzeArgs;
+
+use super::{board_renderer, load_game_state};
+
+fn print_report(game_state: &GameState, report: &SearchReport, multi_pv: usize) {
+    let nps = report.nodes as f64 / report.elapsed.as_secs_f64().max(1e-6);
+    println!(
+        "Depth {} | {} nodes | {:.0} nodes/s | {:?}",
+        report.depth, report.nodes, nps, report.elapsed
+    );
+    for (index, root_move) in report.root_moves.iter().take(multi_pv).enumerate() {
+        println!(
+            "  {}. {} {:>+7}  {}",
+            index + 1,
+            format_move_in(game_state, &root_move.root_move),
+            root_move.score,
+            format_variation(game_state, &root_move.principal_variation)
+        );
+    }
+}
+
+pub fn run(analyze_args: AnalyzeArgs, config: &Config) -> Result<(), Error> {
+    let game_state = load_game_state(
+        analyze_args.position.as_deref().unwrap_or_default(),
+        analyze_args.replay.as_deref(),
+        analyze_args.turn,
+    )?;
+
+    println!("{}", board_renderer(config)?.render(&game_state));
+    println!("Result: {:?}", game_state.get_result());
+
+    let possible_moves = game_state.calculate_annotated_moves(&game_state.get_current_team());
+    let possible_moves_notation = possible_moves
+        .iter()
+        .map(format_annotated_move)
+        .collect::<Vec<String>>();
+    println!(
+        "{} possible moves: {}\n",
+        possible_moves.len(),
+        possible_moves_notation.join(", ")
+    );
+
+    // Without any limit, search for the configured move time
+    let move_time = match (analyze_args.time_ms, analyze_args.depth) {
+        (Some(time_ms), _) => Some(Duration::from_millis(time_ms)),
+        (None, Some(_)) => None,
+        (None, None) => Some(Duration::from_millis(config.engine.move_time_ms())),
+    };
+    let mut search = Search::new(SearchLimits {
+        max_depth: analyze_args.depth,
+        move_time,
+    });
+    let multi_pv = analyze_args.multi_pv;
+    let report = search.run(&game_state, multi_pv, &mut |report| {
+        print_report(&game_state, report, multi_pv)
+    });
+
+    match report.as_ref().and_then(|report| report.best_move()) {
+        Some(best_move) => println!(
+            "\nBest move: {}",
+            format_move_in(&game_state, &best_move.root_move)
+        ),
+        None => println!("\nNo move to search in this position"),
+    }
+
+    Ok(())
+}
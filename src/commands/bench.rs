@@ -0,0 +1,108 @@
+use std::time::{Duration, Instant};
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use socha_2022_rust::game::{
+    game_state::GameState,
+    notation::{parse_position, START_POSITION},
+    perft::perft,
+};
+use socha_2022_rust::logic::{selfplay::random_game_positions, strategy::strategy_from_name};
+use socha_2022_rust::{config::Config, Error};
+
+use crate::args::tools::BenchArgs;
+
+/// The number of random games whose positions the strategy is measured on.
+/// The games are seeded, so every run measures the same positions.
+const STRATEGY_BENCH_GAMES: u64 = 3;
+/// How often the moves of every position are generated when comparing the
+/// move generators.
+const GENERATOR_BENCH_ITERATIONS: u32 = 200;
+
+fn measure_generator(
+    name: &str,
+    positions: &[GameState],
+    generate: impl Fn(&GameState) -> usize,
+) -> Duration {
+    let start_time = Instant::now();
+    let mut moves = 0;
+    for _ in 0..GENERATOR_BENCH_ITERATIONS {
+        for position in positions.iter() {
+            moves += generate(position);
+        }
+    }
+    let elapsed = start_time.elapsed();
+    println!(
+        "  {:<28} {} moves in {:?} ({:.0} moves/s)",
+        name,
+        moves,
+        elapsed,
+        moves as f64 / elapsed.as_secs_f64()
+    );
+    elapsed
+}
+
+/// Compares the speed of the lazy legal move generator with the one of
+/// `calculate_possible_moves` on the positions of random games. That both
+/// generate the same moves is checked by the movegen tests.
+fn compare_move_generators(positions: &[GameState]) {
+    println!("Move generators on {} positions:", positions.len());
+    let possible_moves_time = measure_generator("calculate_possible_moves", positions, |position| {
+        position
+            .calculate_possible_moves(&position.get_current_team())
+            .len()
+    });
+    let legal_moves_time = measure_generator("legal_moves", positions, |position| {
+        position.legal_moves().count()
+    });
+    measure_generator("legal_captures", positions, |position| {
+        position.legal_captures().count()
+    });
+    println!(
+        "  legal_moves is {:.1}x as fast as calculate_possible_moves",
+        possible_moves_time.as_secs_f64() / legal_moves_time.as_secs_f64().max(1e-9)
+    );
+}
+
+pub fn run(bench_args: BenchArgs, config: &Config) -> Result<(), Error> {
+    let game_state = parse_position(START_POSITION)?;
+    let start_time = Instant::now();
+    let nodes = perft(&game_state, bench_args.depth);
+    let elapsed = start_time.elapsed();
+    println!(
+        "Move generation: {} nodes at depth {} in {:?} ({:.0} nodes/s)",
+        nodes,
+        bench_args.depth,
+        elapsed,
+        nodes as f64 / elapsed.as_secs_f64()
+    );
+
+    let mut positions = Vec::new();
+    for seed in 0..STRATEGY_BENCH_GAMES {
+        positions.extend(random_game_positions(&mut StdRng::seed_from_u64(seed))?);
+    }
+    compare_move_generators(&positions);
+
+    let mut strategy = strategy_from_name(&config.strategy, &config.engine)?;
+    let mut total_time = Duration::ZERO;
+    let mut total_moves = 0;
+    for position in positions.iter() {
+        let start_time = Instant::now();
+        if strategy.calculate_move(position).is_some() {
+            total_time += start_time.elapsed();
+            total_moves += 1;
+        }
+    }
+
+    if total_moves > 0 {
+        println!(
+            "Strategy '{}': {} moves in {:?} ({:?} per move)",
+            config.strategy,
+            total_moves,
+            total_time,
+            total_time / total_moves
+        );
+    }
+
+    Ok(())
+}
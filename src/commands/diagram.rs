@@ -0,0 +1,33 @@
+use socha_2022_rust::game::{
+    diagram::{save_diagram, DiagramOptions},
+    notation::parse_move,
+};
+use socha_2022_rust::Error;
+
+use crate::args::tools::DiagramArgs;
+
+use super::load_game_state;
+
+pub fn run(diagram_args: DiagramArgs) -> Result<(), Error> {
+    let game_state = load_game_state(
+        &diagram_args.position,
+        diagram_args.replay.as_deref(),
+        diagram_args.turn,
+    )?;
+
+    let candidate_moves = match &diagram_args.candidates {
+        Some(candidates) => candidates
+            .split(',')
+            .map(|candidate| parse_move(candidate.trim()))
+            .collect::<Result<Vec<_>, Error>>()?,
+        None => Vec::new(),
+    };
+    let options = DiagramOptions {
+        square_size: diagram_args.size,
+        candidate_moves,
+    };
+
+    save_diagram(&game_state, &options, &diagram_args.output)?;
+    println!("Wrote diagram of turn {} to {}", game_state.turn, diagram_args.output);
+    Ok(())
+}
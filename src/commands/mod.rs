@@ -0,0 +1,46 @@
+pub mod analyze;
+pub mod bench;
+pub mod diagram;
+pub mod perft;
+pub mod play;
+pub mod replay;
+pub mod selfplay;
+pub mod solve;
+pub mod telemetry;
+pub mod tui;
+pub mod viewer;
+
+use socha_2022_rust::game::{
+    game_state::GameState,
+    notation::parse_position,
+    render::{BoardRenderer, RenderStyle},
+    replay::load_replay,
+};
+use socha_2022_rust::{config::Config, Error};
+
+/// Creates the renderer for the board style of the configuration.
+pub fn board_renderer(config: &Config) -> Result<BoardRenderer, Error> {
+    Ok(BoardRenderer::new(RenderStyle::from_name(&config.board_style)?))
+}
+
+/// Parses the position, or takes the game state of the given turn (the last
+/// one by default) from a server replay or game recording.
+pub fn load_game_state(
+    position: &str,
+    replay: Option<&str>,
+    turn: Option<u32>,
+) -> Result<GameState, Error> {
+    let replay = match replay {
+        Some(replay) => replay,
+        None => return parse_position(position),
+    };
+
+    let game_states = load_replay(replay)?;
+    let game_state = match turn {
+        Some(turn) => game_states.into_iter().find(|game_state| game_state.turn == turn),
+        None => game_states.into_iter().last(),
+    };
+    game_state.ok_or_else(|| {
+        Error::SimpleError(format!("The replay {} contains no such turn", replay))
+    })
+}
@@ -0,0 +1,22 @@
+use std::time::Instant;
+
+use socha_2022_rust::game::{notation::parse_position, perft::perft};
+use socha_2022_rust::{config::Config, Error};
+
+use crate::args::tools::PerftArgs;
+
+use super::board_renderer;
+
+pub fn run(perft_args: PerftArgs, config: &Config) -> Result<(), Error> {
+    let game_state = parse_position(&perft_args.position)?;
+    println!("{}", board_renderer(config)?.render(&game_state));
+
+    for depth in 1..=perft_args.depth {
+        let start_time = Instant::now();
+        let nodes = perft(&game_state, depth);
+        let elapsed = start_time.elapsed();
+        println!("Depth {}: {} nodes in {:?}", depth, nodes, elapsed);
+    }
+
+    Ok(())
+}
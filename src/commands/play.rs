@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use socha_2022_rust::logic::{
+    admin, client, logic::Logic, observer::Observer, strategy::strategy_from_name,
+};
+use socha_2022_rust::{config::Config, Error};
+
+use crate::args::{client::ClientArgs, global::GlobalArgs};
+
+use super::board_renderer;
+
+pub fn run(client_args: ClientArgs, global_args: &GlobalArgs, config: &Config) -> Result<(), Error> {
+    if client_args.prepare {
+        let password = client_args.password.as_ref().ok_or_else(|| {
+            Error::SimpleError(String::from(
+                "Preparing a game requires the server password (--password)",
+            ))
+        })?;
+        let player_names = (String::from("Player 1"), String::from("Player 2"));
+        let prepared = admin::prepare_game(&config.host, config.port, password, player_names)?;
+        admin::print_reservations(&prepared);
+
+        if client_args.launch {
+            admin::launch_clients(
+                &config.host,
+                config.port,
+                &global_args.raw_args,
+                &prepared,
+            )?;
+        }
+        return Ok(());
+    }
+
+    if let Some(room_id) = client_args.observe.clone() {
+        let password = client_args.password.as_ref().ok_or_else(|| {
+            Error::SimpleError(String::from(
+                "Observing a game requires the server password (--password)",
+            ))
+        })?;
+        let observer = Observer::new(room_id, board_renderer(config)?);
+        return client::observe_game(&config.host, config.port, password, observer);
+    }
+
+    let mut logic = Logic::with_strategy(strategy_from_name(&config.strategy, &config.engine)?);
+    if let Some(record_directory) = &config.record_directory {
+        logic.enable_recording(PathBuf::from(record_directory));
+    }
+    if let Some(telemetry_path) = &config.telemetry_path {
+        logic.enable_telemetry(PathBuf::from(telemetry_path))?;
+    }
+
+    client::play_game(
+        &config.host,
+        config.port,
+        client_args.reservation.clone(),
+        logic,
+    )
+}
@@ -0,0 +1,37 @@
+use socha_2022_rust::game::replay::load_replay;
+use socha_2022_rust::logic::{
+    crosscheck::cross_check_all, recording::load_recording, replay, strategy::strategy_from_name,
+};
+use socha_2022_rust::{config::Config, Error};
+
+use crate::args::tools::ReplayArgs;
+
+use super::board_renderer;
+
+pub fn run(replay_args: ReplayArgs, config: &Config) -> Result<(), Error> {
+    let game_states = load_replay(&replay_args.file)?;
+    log::info!("Loaded {} game states from {}", game_states.len(), replay_args.file);
+
+    if replay_args.check {
+        let divergences = cross_check_all(&game_states);
+        for divergence in divergences.iter() {
+            println!("{}", divergence.report());
+        }
+        println!(
+            "{} of {} game states differ from our rules",
+            divergences.len(),
+            game_states.len().saturating_sub(1)
+        );
+        return Ok(());
+    }
+
+    let recording = load_recording(&replay_args.file).ok();
+    let mut strategy = strategy_from_name(&config.strategy, &config.engine)?;
+    replay::step_through_replay(
+        &game_states,
+        recording.as_ref(),
+        strategy.as_mut(),
+        &board_renderer(config)?,
+    );
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use socha_2022_rust::game::{
+    board::Board,
+    game_state::{GameState, GameStateResult},
+    notation::parse_position,
+};
+use socha_2022_rust::logic::{selfplay::play_game, strategy::strategy_from_name};
+use socha_2022_rust::{config::Config, Error};
+use socha_2022_rust::PlayerTeam;
+
+use crate::args::tools::SelfPlayArgs;
+
+pub fn run(selfplay_args: SelfPlayArgs, config: &Config) -> Result<(), Error> {
+    let strategy_one_name = selfplay_args
+        .strategy_one
+        .clone()
+        .unwrap_or_else(|| config.strategy.clone());
+    let strategy_two_name = selfplay_args
+        .strategy_two
+        .clone()
+        .unwrap_or_else(|| config.strategy.clone());
+    let mut strategy_one = strategy_from_name(&strategy_one_name, &config.engine)?;
+    let mut strategy_two = strategy_from_name(&strategy_two_name, &config.engine)?;
+
+    let mut wins = (0, 0);
+    let mut draws = 0;
+    let mut undecided = 0;
+
+    for game in 1..=selfplay_args.games {
+        let start_state = match &selfplay_args.position {
+            Some(position) => parse_position(position)?,
+            None => GameState::new(Board::random_start_position()),
+        };
+
+        let final_state = play_game(start_state, strategy_one.as_mut(), strategy_two.as_mut());
+        let result = final_state.get_result();
+        println!(
+            "Game {}: {:?} after turn {} with ambers {:?} ({:?})",
+            game,
+            result,
+            final_state.turn,
+            final_state.ambers,
+            final_state.get_end_reason()
+        );
+
+        match result {
+            GameStateResult::Player(PlayerTeam::One) => wins.0 += 1,
+            GameStateResult::Player(PlayerTeam::Two) => wins.1 += 1,
+            GameStateResult::Draw => draws += 1,
+            GameStateResult::Nothing => undecided += 1,
+        }
+    }
+
+    println!(
+        "'{}' (team one) vs. '{}' (team two): {} - {} ({} draws, {} undecided)",
+        strategy_one_name, strategy_two_name, wins.0, wins.1, draws, undecided
+    );
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use std::time::{Duration, Instant};
+
+use socha_2022_rust::game::{
+    game_state::GameState,
+    notation::{format_move_in, format_variation},
+};
+use socha_2022_rust::logic::proof::{ProofLimits, ProofResult, ProofSearch};
+use socha_2022_rust::logic::solver::{Solver, SolverResult};
+use socha_2022_rust::{config::Config, Error};
+
+use crate::args::tools::SolveArgs;
+
+use super::{board_renderer, load_game_state};
+
+fn run_proof_search(game_state: &GameState, move_time: Option<Duration>, config: &Config) {
+    let mut proof_search = ProofSearch::new(ProofLimits {
+        memory_mb: config.engine.hash_size_mb,
+        move_time,
+        max_nodes: None,
+    });
+    let start_time = Instant::now();
+    let result = proof_search.solve(game_state);
+    println!(
+        "{} nodes in {:?}, {} table entries",
+        proof_search.nodes(),
+        start_time.elapsed(),
+        proof_search.table_size()
+    );
+
+    let team = game_state.get_current_team();
+    match result {
+        ProofResult::Win(r#move) => println!(
+            "{:?} wins with {}",
+            team,
+            format_move_in(game_state, &r#move)
+        ),
+        ProofResult::Draw(r#move) => println!(
+            "{:?} holds the draw with {}",
+            team,
+            format_move_in(game_state, &r#move)
+        ),
+        ProofResult::Loss => println!("{:?} loses against every defence", team),
+        ProofResult::Unknown => println!("The result couldn't be proven within the limits"),
+    }
+}
+
+pub fn run(solve_args: SolveArgs, config: &Config) -> Result<(), Error> {
+    let game_state = load_game_state(
+        solve_args.position.as_deref().unwrap_or_default(),
+        solve_args.replay.as_deref(),
+        solve_args.turn,
+    )?;
+
+    println!("{}", board_renderer(config)?.render(&game_state));
+
+    let move_time = solve_args.time_ms.map(Duration::from_millis);
+    if solve_args.proof {
+        run_proof_search(&game_state, move_time, config);
+        return Ok(());
+    }
+
+    let mut solver = Solver::new(solve_args.plies);
+    if let Some(move_time) = move_time {
+        solver = solver.with_move_time(move_time);
+    }
+    let start_time = Instant::now();
+    let result = solver.solve(&game_state);
+    println!("{} nodes in {:?}", solver.nodes(), start_time.elapsed());
+
+    match result {
+        SolverResult::Won(line) => println!(
+            "{:?} wins in {} plies: {}",
+            game_state.get_current_team(),
+            line.len(),
+            format_variation(&game_state, &line)
+        ),
+        SolverResult::NotFound => println!("No forced win within {} plies", solve_args.plies),
+        SolverResult::Aborted => println!("The time ran out before a forced win was found"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,10 @@
+use socha_2022_rust::logic::telemetry::{load_telemetry, TelemetrySummary};
+use socha_2022_rust::Error;
+
+use crate::args::tools::TelemetryArgs;
+
+pub fn run(telemetry_args: TelemetryArgs) -> Result<(), Error> {
+    let records = load_telemetry(&telemetry_args.file)?;
+    println!("{}", TelemetrySummary::from_records(&records));
+    Ok(())
+}
@@ -0,0 +1,297 @@
+use std::io::{self, Stdout, Write};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute, queue,
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use socha_2022_rust::game::{
+    board::Board,
+    game_state::GameState,
+    notation::{format_move_in, format_square, format_variation, parse_move, parse_position, parse_square},
+    render::BoardRenderer,
+};
+use socha_2022_rust::logic::{
+    session::{EngineMove, PlaySession},
+    strategy::{strategy_from_name, Strategy},
+};
+use socha_2022_rust::{config::Config, Coordinates, Error};
+
+use crate::args::tools::TuiArgs;
+
+use super::board_renderer;
+
+const KEYS: &str = "Arrows: move cursor | Enter/Space: select piece and target | \
+                    type a move like a2-b3 + Enter | u: undo | Esc: cancel | q: quit";
+
+struct Tui {
+    session: PlaySession,
+    renderer: BoardRenderer,
+    cursor: Coordinates,
+    selected: Option<Coordinates>,
+    input: String,
+    message: String,
+    analysis: Option<EngineMove>,
+}
+
+enum Action {
+    Continue,
+    Quit,
+}
+
+fn io_result<T>(result: io::Result<T>) -> Result<T, Error> {
+    result.map_err(Error::IOError)
+}
+
+fn format_engine_move(engine_move: &EngineMove) -> String {
+    let search_info = match &engine_move.search_info {
+        Some(search_info) => format!(
+            " | depth {} | score {} | PV {}",
+            search_info.depth,
+            search_info.score,
+            format_variation(&engine_move.game_state, &search_info.principal_variation)
+        ),
+        None => String::new(),
+    };
+    format!(
+        "{}{}",
+        format_move_in(&engine_move.game_state, &engine_move.engine_move),
+        search_info
+    )
+}
+
+impl Tui {
+    fn selected_targets(&self) -> Vec<Coordinates> {
+        match &self.selected {
+            Some(selected) => self
+                .session
+                .legal_moves()
+                .into_iter()
+                .filter(|legal_move| &legal_move.from == selected)
+                .map(|legal_move| legal_move.to)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn draw(&self, stdout: &mut Stdout) -> Result<(), Error> {
+        let game_state = &self.session.game_state;
+
+        // While a piece is selected, only its targets are highlighted
+        let (highlighted_squares, last_move) = match &self.selected {
+            Some(selected) => {
+                let mut squares = self.selected_targets();
+                squares.push(selected.clone());
+                (squares, None)
+            }
+            None => (Vec::new(), game_state.last_move.as_ref()),
+        };
+        let renderer = self
+            .renderer
+            .clone()
+            .with_highlighted_squares(highlighted_squares)
+            .with_cursor(Some(self.cursor.clone()));
+
+        let mut lines = vec![
+            renderer.render_status_line(game_state),
+            renderer.render_board(&game_state.board, last_move),
+            String::new(),
+            format!(
+                "You play {:?} | Cursor {} | Selected {} | Input {}",
+                self.session.human_team,
+                format_square(&self.cursor),
+                self.selected
+                    .as_ref()
+                    .map(format_square)
+                    .unwrap_or_else(|| String::from("-")),
+                self.input
+            ),
+        ];
+        if let Some(engine_move) = &self.session.last_engine_move {
+            lines.push(format!("Engine played {}", format_engine_move(engine_move)));
+        }
+        match &self.analysis {
+            Some(analysis) => lines.push(format!(
+                "Analysis: engine suggests {}",
+                format_engine_move(analysis)
+            )),
+            None => lines.push(String::from("Analysis: -")),
+        }
+        if self.session.is_over() {
+            lines.push(format!(
+                "Game over after turn {}: {:?} ({:?})",
+                game_state.turn,
+                game_state.get_result(),
+                game_state.get_end_reason()
+            ));
+        }
+        lines.push(self.message.clone());
+        lines.push(String::from(KEYS));
+
+        io_result(queue!(stdout, Clear(ClearType::All), cursor::MoveTo(0, 0)))?;
+        // Raw mode needs explicit carriage returns
+        io_result(write!(stdout, "{}", lines.join("\n").replace('\n', "\r\n")))?;
+        io_result(stdout.flush())
+    }
+
+    fn analyze(&mut self, analysis_strategy: &mut dyn Strategy) {
+        self.analysis = if self.session.is_human_to_move() {
+            analysis_strategy
+                .calculate_move(&self.session.game_state)
+                .map(|engine_move| EngineMove {
+                    engine_move,
+                    search_info: analysis_strategy.search_info(),
+                    game_state: self.session.game_state.clone(),
+                })
+        } else {
+            None
+        };
+    }
+
+    fn play_move(&mut self, text: &str) {
+        let result = parse_move(text).and_then(|r#move| self.session.play_human_move(&r#move));
+        self.message = match result {
+            Ok(_) => format!("You played {}", text),
+            Err(error) => format!("{}: {}", text, error),
+        };
+        self.selected = None;
+    }
+
+    fn select(&mut self) {
+        let cursor = self.cursor.clone();
+        if self.selected.is_some() && self.selected_targets().contains(&cursor) {
+            let from = self.selected.clone().unwrap();
+            self.play_move(&format!("{}-{}", format_square(&from), format_square(&cursor)));
+            return;
+        }
+
+        let has_moves = self
+            .session
+            .legal_moves()
+            .iter()
+            .any(|legal_move| legal_move.from == cursor);
+        if self.session.is_human_to_move() && has_moves {
+            self.selected = Some(cursor);
+            self.message.clear();
+        } else {
+            self.selected = None;
+            self.message = String::from("No piece of yours that can move there");
+        }
+    }
+
+    fn handle_key(&mut self, key_code: KeyCode) -> Action {
+        match key_code {
+            KeyCode::Char('q') => return Action::Quit,
+            KeyCode::Char('u') => {
+                self.selected = None;
+                self.message = if self.session.undo() {
+                    String::from("Took back the last move")
+                } else {
+                    String::from("Nothing to undo")
+                };
+            }
+            KeyCode::Esc => {
+                self.selected = None;
+                self.input.clear();
+                self.message.clear();
+            }
+            KeyCode::Up => self.cursor.y = (self.cursor.y - 1).max(0),
+            KeyCode::Down => self.cursor.y = (self.cursor.y + 1).min(7),
+            KeyCode::Left => self.cursor.x = (self.cursor.x - 1).max(0),
+            KeyCode::Right => self.cursor.x = (self.cursor.x + 1).min(7),
+            KeyCode::Char(character) if matches!(character, 'a'..='h' | '1'..='8' | '-') => {
+                self.input.push(character)
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Enter if !self.input.is_empty() => {
+                let input = std::mem::take(&mut self.input);
+                match parse_square(&input) {
+                    Ok(square) => {
+                        self.cursor = square;
+                        self.select();
+                    }
+                    Err(_) => self.play_move(&input),
+                }
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => self.select(),
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    fn run(
+        &mut self,
+        stdout: &mut Stdout,
+        strategy: &mut dyn Strategy,
+        analysis_strategy: &mut dyn Strategy,
+    ) -> Result<(), Error> {
+        let mut position_changed = true;
+        loop {
+            if !self.session.is_over() && !self.session.is_human_to_move() {
+                let message = std::mem::replace(&mut self.message, String::from("Engine is thinking..."));
+                self.draw(stdout)?;
+                self.message = match self.session.play_engine_move(strategy) {
+                    Ok(_) => message,
+                    Err(error) => error.to_string(),
+                };
+                position_changed = true;
+            }
+            if position_changed {
+                self.analyze(analysis_strategy);
+            }
+            self.draw(stdout)?;
+
+            let turn = self.session.game_state.turn;
+            if let Event::Key(key) = io_result(event::read())? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                if let Action::Quit = self.handle_key(key.code) {
+                    return Ok(());
+                }
+            }
+            position_changed = self.session.game_state.turn != turn;
+        }
+    }
+}
+
+pub fn run(tui_args: TuiArgs, config: &Config) -> Result<(), Error> {
+    let game_state = match &tui_args.position {
+        Some(position) => parse_position(position)?,
+        None => GameState::new(Board::random_start_position()),
+    };
+    let mut strategy = strategy_from_name(&config.strategy, &config.engine)?;
+    let mut analysis_strategy = strategy_from_name(&config.strategy, &config.engine)?;
+
+    let mut tui = Tui {
+        session: PlaySession::new(game_state, tui_args.team.clone()),
+        renderer: board_renderer(config)?,
+        cursor: Coordinates::new(tui_args.team.start_line(), 0),
+        selected: None,
+        input: String::new(),
+        message: String::new(),
+        analysis: None,
+    };
+
+    let mut stdout = io::stdout();
+    io_result(terminal::enable_raw_mode())?;
+    io_result(execute!(stdout, EnterAlternateScreen, cursor::Hide))?;
+
+    let result = tui.run(&mut stdout, strategy.as_mut(), analysis_strategy.as_mut());
+
+    io_result(execute!(stdout, cursor::Show, LeaveAlternateScreen))?;
+    io_result(terminal::disable_raw_mode())?;
+
+    let game_state = &tui.session.game_state;
+    println!(
+        "Stopped after turn {} with ambers {:?}: {:?}",
+        game_state.turn,
+        game_state.ambers,
+        game_state.get_result()
+    );
+    result
+}
@@ -0,0 +1,36 @@
+use std::fs;
+use std::path::Path;
+
+use socha_2022_rust::game::replay::load_replay;
+use socha_2022_rust::logic::{
+    recording::load_recording,
+    strategy::strategy_from_name,
+    viewer::{collect_viewer_turns, render_viewer_html},
+};
+use socha_2022_rust::{config::Config, Error};
+
+use crate::args::tools::ViewerArgs;
+
+pub fn run(viewer_args: ViewerArgs, config: &Config) -> Result<(), Error> {
+    let game_states = load_replay(&viewer_args.file)?;
+    let recording = load_recording(&viewer_args.file).ok();
+
+    let turns = if viewer_args.analyze {
+        let mut strategy = strategy_from_name(&config.strategy, &config.engine)?;
+        collect_viewer_turns(&game_states, recording.as_ref(), Some(strategy.as_mut()))
+    } else {
+        collect_viewer_turns(&game_states, recording.as_ref(), None)
+    };
+
+    let title = Path::new(&viewer_args.file)
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_else(|| viewer_args.file.clone());
+    let html = render_viewer_html(&title, &turns);
+    if let Err(error) = fs::write(&viewer_args.output, html) {
+        return Err(Error::IOError(error));
+    }
+
+    println!("Wrote {} turns to {}", turns.len(), viewer_args.output);
+    Ok(())
+}
@@ -0,0 +1,126 @@
+//! Attack maps and threat queries. A piece attacks every square it could move
+//! to if an opponent piece stood there, no matter which team is to move.
+
+use crate::util::coordinates::Coordinates;
+use crate::xml::enums::PlayerTeam;
+
+use super::game_state::GameState;
+use super::movegen::piece_offsets;
+use super::piece::Piece;
+
+/// The number of pieces of a team attacking each square.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttackMap {
+    counts: [[u8; 8]; 8],
+}
+
+impl AttackMap {
+    pub fn attack_count(&self, square: &Coordinates) -> u8 {
+        if !square.in_bounds() {
+            return 0;
+        }
+        self.counts[square.y as usize][square.x as usize]
+    }
+
+    pub fn is_attacked(&self, square: &Coordinates) -> bool {
+        self.attack_count(square) > 0
+    }
+
+    /// The attacked squares ordered by rank, then file.
+    pub fn squares(&self) -> Vec<Coordinates> {
+        let mut squares = Vec::new();
+        for y in 0..8 {
+            for x in 0..8 {
+                if self.counts[y as usize][x as usize] > 0 {
+                    squares.push(Coordinates::new(x, y));
+                }
+            }
+        }
+        squares
+    }
+}
+
+fn attacked_by(from: &Coordinates, piece: &Piece) -> impl Iterator<Item = Coordinates> {
+    let from = from.clone();
+    piece_offsets(&piece.piece_type, &piece.team)
+        .iter()
+        .map(move |(dx, dy)| Coordinates::new(from.x + dx, from.y + dy))
+        .filter(|square| square.in_bounds())
+}
+
+impl GameState {
+    pub fn attack_map(&self, team: &PlayerTeam) -> AttackMap {
+        let mut counts = [[0; 8]; 8];
+        for (from, piece) in self.board.pieces.iter() {
+            if &piece.team != team {
+                continue;
+            }
+            for square in attacked_by(from, piece) {
+                counts[square.y as usize][square.x as usize] += 1;
+            }
+        }
+        AttackMap { counts }
+    }
+
+    /// The squares attacked by at least one piece of the team.
+    pub fn attacked_squares(&self, team: &PlayerTeam) -> Vec<Coordinates> {
+        self.attack_map(team).squares()
+    }
+
+    /// The positions of all pieces of both teams attacking the square.
+    pub fn attackers_of(&self, square: &Coordinates) -> Vec<Coordinates> {
+        let mut attackers = self
+            .board
+            .pieces
+            .iter()
+            .filter(|(from, piece)| attacked_by(from, piece).any(|attacked| &attacked == square))
+            .map(|(from, _)| from.clone())
+            .collect::<Vec<Coordinates>>();
+        attackers.sort_by_key(|attacker| (attacker.y, attacker.x));
+        attackers
+    }
+
+    /// The positions of the pieces of the team attacking the square.
+    pub fn attackers_of_team(&self, square: &Coordinates, team: &PlayerTeam) -> Vec<Coordinates> {
+        self.attackers_of(square)
+            .into_iter()
+            .filter(|attacker| {
+                self.board
+                    .get_piece_at(attacker)
+                    .is_some_and(|piece| &piece.team == team)
+            })
+            .collect()
+    }
+
+    /// Whether the piece on the square is attacked by a piece of its own team,
+    /// which could recapture it. Empty squares are not defended.
+    pub fn is_defended(&self, square: &Coordinates) -> bool {
+        match self.board.get_piece_at(square) {
+            Some(piece) => !self.attackers_of_team(square, &piece.team).is_empty(),
+            None => false,
+        }
+    }
+
+    /// The positions of the pieces of the team that the opponent attacks,
+    /// ordered by rank, then file.
+    pub fn threatened_pieces(&self, team: &PlayerTeam) -> Vec<Coordinates> {
+        let opponent_attacks = self.attack_map(&team.opponent());
+        let mut threatened = self
+            .board
+            .pieces
+            .iter()
+            .filter(|(square, piece)| &piece.team == team && opponent_attacks.is_attacked(square))
+            .map(|(square, _)| square.clone())
+            .collect::<Vec<Coordinates>>();
+        threatened.sort_by_key(|square| (square.y, square.x));
+        threatened
+    }
+
+    /// The threatened pieces of the team that no own piece defends.
+    pub fn hanging_pieces(&self, team: &PlayerTeam) -> Vec<Coordinates> {
+        self.threatened_pieces(team)
+            .into_iter()
+            .filter(|square| !self.is_defended(square))
+            .collect()
+    }
+}
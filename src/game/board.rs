@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
+use rand::{seq::SliceRandom, thread_rng, Rng};
+
 use crate::xml::enums::{PieceType, PlayerTeam};
 use crate::xml::server::state::Board as XmlBoard;
 use crate::{
@@ -15,6 +17,53 @@ pub struct Board {
 }
 
 impl Board {
+    /// Creates a start position with the pieces of team one placed on their
+    /// start line in the given order. The pieces of team two are placed
+    /// point-symmetrically on the opposite start line.
+    pub fn start_position(order: &[PieceType; 8]) -> Self {
+        let mut pieces = HashMap::new();
+        for (y, piece_type) in order.iter().enumerate() {
+            let y = y as i32;
+            for team in [PlayerTeam::One, PlayerTeam::Two] {
+                let coordinates = match team {
+                    PlayerTeam::One => Coordinates::new(team.start_line(), y),
+                    PlayerTeam::Two => Coordinates::new(team.start_line(), 7 - y),
+                };
+                let piece = Piece {
+                    piece_type: piece_type.clone(),
+                    team,
+                    count: 1,
+                };
+                pieces.insert(coordinates, piece);
+            }
+        }
+
+        Board { pieces }
+    }
+
+    /// Creates a start position with a random order of pieces, like the
+    /// server does.
+    pub fn random_start_position() -> Self {
+        Self::random_start_position_with(&mut thread_rng())
+    }
+
+    /// Creates a random start position with the given random number
+    /// generator, so a seeded generator always creates the same position.
+    pub fn random_start_position_with<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut order = [
+            PieceType::Herzmuschel,
+            PieceType::Herzmuschel,
+            PieceType::Moewe,
+            PieceType::Moewe,
+            PieceType::Seestern,
+            PieceType::Seestern,
+            PieceType::Robbe,
+            PieceType::Robbe,
+        ];
+        order.shuffle(rng);
+        Self::start_position(&order)
+    }
+
     pub fn get_piece_at(&self, coords: &Coordinates) -> Option<&Piece> {
         self.pieces.get(coords)
     }
@@ -25,11 +74,11 @@ impl Board {
         }
     }
 
-    pub fn get_piece_at_mut<'a>(&'a mut self, coords: Coordinates) -> Option<&'a mut Piece> {
+    pub fn get_piece_at_mut(&mut self, coords: Coordinates) -> Option<&mut Piece> {
         self.pieces.get_mut(&coords)
     }
 
-    pub fn get_piece_at_ref_mut<'a>(&'a mut self, coords: &Coordinates) -> Option<&'a mut Piece> {
+    pub fn get_piece_at_ref_mut(&mut self, coords: &Coordinates) -> Option<&mut Piece> {
         let coords = coords.clone();
         self.get_piece_at_mut(coords)
     }
@@ -82,7 +131,7 @@ impl Display for Board {
                     }
                 }
             }
-            write!(fmt, "\n")?;
+            writeln!(fmt)?;
         }
 
         Ok(())
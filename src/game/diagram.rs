@@ -0,0 +1,327 @@
+//! Board diagrams as SVG images, e.g. for write-ups and bug reports. With the
+//! `png` feature, diagrams can be rasterized to PNG as well.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use crate::util::{coordinates::Coordinates, error::Error};
+use crate::xml::enums::{PieceType, PlayerTeam};
+
+use super::{game_state::GameState, moves::Move, piece::Piece};
+
+const LIGHT_SQUARE_COLOR: &str = "#e8dcc0";
+const DARK_SQUARE_COLOR: &str = "#b7a57e";
+const TEAM_ONE_COLOR: &str = "#c0392b";
+const TEAM_TWO_COLOR: &str = "#2c6fbb";
+const LAST_MOVE_COLOR: &str = "#f39c12";
+const CANDIDATE_MOVE_COLOR: &str = "#27ae60";
+
+#[derive(Debug, Clone)]
+pub struct DiagramOptions {
+    /// The edge length of a single square in pixels.
+    pub square_size: u32,
+    /// Moves that are drawn as additional arrows, e.g. the best moves of a
+    /// search.
+    pub candidate_moves: Vec<Move>,
+}
+
+impl Default for DiagramOptions {
+    fn default() -> Self {
+        Self {
+            square_size: 64,
+            candidate_moves: Vec::new(),
+        }
+    }
+}
+
+struct Canvas {
+    square_size: f64,
+    margin: f64,
+}
+
+impl Canvas {
+    fn size(&self) -> f64 {
+        self.margin * 2.0 + self.square_size * 8.0
+    }
+
+    /// The center of a square. Like the terminal renderer, y = 0 is the top
+    /// row.
+    fn center(&self, coordinates: &Coordinates) -> (f64, f64) {
+        (
+            self.margin + (coordinates.x as f64 + 0.5) * self.square_size,
+            self.margin + (coordinates.y as f64 + 0.5) * self.square_size,
+        )
+    }
+}
+
+fn team_color(team: &PlayerTeam) -> &'static str {
+    match team {
+        PlayerTeam::One => TEAM_ONE_COLOR,
+        PlayerTeam::Two => TEAM_TWO_COLOR,
+    }
+}
+
+fn piece_letter(piece_type: &PieceType) -> char {
+    match piece_type {
+        PieceType::Herzmuschel => 'H',
+        PieceType::Moewe => 'M',
+        PieceType::Seestern => 'S',
+        PieceType::Robbe => 'R',
+    }
+}
+
+fn star_points(cx: f64, cy: f64, outer_radius: f64, inner_radius: f64) -> String {
+    (0..10)
+        .map(|index| {
+            let radius = if index % 2 == 0 { outer_radius } else { inner_radius };
+            let angle = std::f64::consts::PI * (index as f64 / 5.0 - 0.5);
+            format!("{:.1},{:.1}", cx + radius * angle.cos(), cy + radius * angle.sin())
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// The outline of a piece icon with the given center and radius.
+fn piece_shape(piece_type: &PieceType, cx: f64, cy: f64, r: f64, style: &str) -> String {
+    match piece_type {
+        PieceType::Herzmuschel => format!(
+            "<path d=\"M {:.1} {:.1} C {:.1} {:.1} {:.1} {:.1} {:.1} {:.1} C {:.1} {:.1} {:.1} {:.1} {:.1} {:.1} Z\" {}/>",
+            cx, cy + 0.75 * r,
+            cx - 1.3 * r, cy - 0.1 * r, cx - 0.6 * r, cy - 1.0 * r, cx, cy - 0.4 * r,
+            cx + 0.6 * r, cy - 1.0 * r, cx + 1.3 * r, cy - 0.1 * r, cx, cy + 0.75 * r,
+            style
+        ),
+        PieceType::Moewe => format!(
+            "<polygon points=\"{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}\" {}/>",
+            cx, cy - 0.85 * r,
+            cx - 0.85 * r, cy + 0.65 * r,
+            cx + 0.85 * r, cy + 0.65 * r,
+            style
+        ),
+        PieceType::Seestern => format!(
+            "<polygon points=\"{}\" {}/>",
+            star_points(cx, cy + 0.05 * r, 0.95 * r, 0.42 * r),
+            style
+        ),
+        PieceType::Robbe => format!(
+            "<ellipse cx=\"{:.1}\" cy=\"{:.1}\" rx=\"{:.1}\" ry=\"{:.1}\" {}/>",
+            cx, cy, 0.85 * r, 0.65 * r, style
+        ),
+    }
+}
+
+fn draw_piece(svg: &mut String, canvas: &Canvas, coordinates: &Coordinates, piece: &Piece) {
+    let (cx, cy) = canvas.center(coordinates);
+    let r = canvas.square_size * 0.38;
+    let style = format!(
+        "fill=\"{}\" stroke=\"#222\" stroke-width=\"{:.1}\"",
+        team_color(&piece.team),
+        canvas.square_size / 32.0
+    );
+
+    // A stacked piece is drawn on top of a shadow copy of itself
+    if piece.is_stacked() {
+        let offset = canvas.square_size * 0.08;
+        svg.push_str(&piece_shape(&piece.piece_type, cx + offset, cy + offset, r, &style));
+        svg.push_str(&piece_shape(&piece.piece_type, cx - offset, cy - offset, r, &style));
+    } else {
+        svg.push_str(&piece_shape(&piece.piece_type, cx, cy, r, &style));
+    }
+
+    let _ = write!(
+        svg,
+        "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"{:.1}\" font-family=\"sans-serif\" font-weight=\"bold\" \
+         fill=\"#fff\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>",
+        cx,
+        cy,
+        canvas.square_size * 0.3,
+        piece_letter(&piece.piece_type)
+    );
+
+    if piece.is_stacked() {
+        let (badge_x, badge_y) = (cx + canvas.square_size * 0.3, cy - canvas.square_size * 0.3);
+        let _ = write!(
+            svg,
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"#fff\" stroke=\"#222\" stroke-width=\"1\"/>\
+             <text x=\"{:.1}\" y=\"{:.1}\" font-size=\"{:.1}\" font-family=\"sans-serif\" fill=\"#222\" \
+             text-anchor=\"middle\" dominant-baseline=\"central\">2</text>",
+            badge_x,
+            badge_y,
+            canvas.square_size * 0.12,
+            badge_x,
+            badge_y,
+            canvas.square_size * 0.16
+        );
+    }
+}
+
+fn draw_arrow(svg: &mut String, canvas: &Canvas, r#move: &Move, color: &str, marker: &str, width: f64) {
+    // A move without a distance has no direction to point the arrow in
+    if r#move.from == r#move.to {
+        return;
+    }
+
+    let (from_x, from_y) = canvas.center(&r#move.from);
+    let (to_x, to_y) = canvas.center(&r#move.to);
+
+    // End the line before the center of the target square so that the arrow
+    // head does not cover the piece completely
+    let (dx, dy) = (to_x - from_x, to_y - from_y);
+    let length = (dx * dx + dy * dy).sqrt();
+    let shortening = (canvas.square_size * 0.3).min(length / 2.0);
+    let (end_x, end_y) = (to_x - dx / length * shortening, to_y - dy / length * shortening);
+
+    let _ = write!(
+        svg,
+        "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{}\" stroke-width=\"{:.1}\" \
+         stroke-linecap=\"round\" opacity=\"0.85\" marker-end=\"url(#{})\"/>",
+        from_x, from_y, end_x, end_y, color, width, marker
+    );
+}
+
+fn arrow_marker(id: &str, color: &str) -> String {
+    format!(
+        "<marker id=\"{}\" viewBox=\"0 0 10 10\" refX=\"5\" refY=\"5\" markerWidth=\"3\" markerHeight=\"3\" \
+         orient=\"auto\"><path d=\"M 0 0 L 10 5 L 0 10 Z\" fill=\"{}\"/></marker>",
+        id, color
+    )
+}
+
+/// Renders the game state as an SVG image with coordinate labels, an arrow
+/// for the last move and arrows for the candidate moves of the options.
+pub fn render_svg(game_state: &GameState, options: &DiagramOptions) -> String {
+    let canvas = Canvas {
+        square_size: options.square_size as f64,
+        margin: options.square_size as f64 * 0.5,
+    };
+    let size = canvas.size();
+    let label_size = canvas.square_size * 0.25;
+
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" viewBox=\"0 0 {:.0} {:.0}\">",
+        size, size, size, size
+    );
+    svg.push_str("<defs>");
+    svg.push_str(&arrow_marker("last-move", LAST_MOVE_COLOR));
+    svg.push_str(&arrow_marker("candidate-move", CANDIDATE_MOVE_COLOR));
+    svg.push_str("</defs>");
+    let _ = write!(svg, "<rect width=\"{:.0}\" height=\"{:.0}\" fill=\"#fff\"/>", size, size);
+
+    for y in 0..8 {
+        for x in 0..8 {
+            let color = if (x + y) % 2 == 0 { LIGHT_SQUARE_COLOR } else { DARK_SQUARE_COLOR };
+            let _ = write!(
+                svg,
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"/>",
+                canvas.margin + x as f64 * canvas.square_size,
+                canvas.margin + y as f64 * canvas.square_size,
+                canvas.square_size,
+                canvas.square_size,
+                color
+            );
+        }
+    }
+
+    let label_style = format!(
+        "font-size=\"{:.1}\" font-family=\"sans-serif\" fill=\"#444\" text-anchor=\"middle\" dominant-baseline=\"central\"",
+        label_size
+    );
+    for index in 0..8 {
+        let (label_x, _) = canvas.center(&Coordinates::new(index, 0));
+        let _ = write!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" {}>{}</text>",
+            label_x,
+            size - canvas.margin / 2.0,
+            label_style,
+            (b'a' + index as u8) as char
+        );
+        let (_, label_y) = canvas.center(&Coordinates::new(0, index));
+        let _ = write!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" {}>{}</text>",
+            canvas.margin / 2.0,
+            label_y,
+            label_style,
+            index + 1
+        );
+    }
+
+    // Sorted, so that the same position always results in the same file
+    let mut pieces = game_state.board.pieces.iter().collect::<Vec<_>>();
+    pieces.sort_by_key(|(coordinates, _)| (coordinates.y, coordinates.x));
+    for (coordinates, piece) in pieces {
+        draw_piece(&mut svg, &canvas, coordinates, piece);
+    }
+
+    if let Some(last_move) = &game_state.last_move {
+        draw_arrow(&mut svg, &canvas, last_move, LAST_MOVE_COLOR, "last-move", canvas.square_size * 0.14);
+    }
+    for candidate_move in options.candidate_moves.iter() {
+        draw_arrow(
+            &mut svg,
+            &canvas,
+            candidate_move,
+            CANDIDATE_MOVE_COLOR,
+            "candidate-move",
+            canvas.square_size * 0.1,
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Rasterizes an SVG image created by `render_svg` to PNG. Labels are only
+/// drawn if a sans-serif system font is installed.
+#[cfg(feature = "png")]
+pub fn render_png(svg: &str) -> Result<Vec<u8>, Error> {
+    let mut svg_options = resvg::usvg::Options::default();
+    svg_options.fontdb_mut().load_system_fonts();
+
+    let tree = match resvg::usvg::Tree::from_str(svg, &svg_options) {
+        Ok(tree) => tree,
+        Err(error) => return Err(Error::SimpleError(format!("Invalid SVG: {}", error))),
+    };
+    let size = tree.size().to_int_size();
+    let mut pixmap = match resvg::tiny_skia::Pixmap::new(size.width(), size.height()) {
+        Some(pixmap) => pixmap,
+        None => return Err(Error::SimpleError(String::from("Invalid image size"))),
+    };
+    resvg::render(&tree, resvg::tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+    match pixmap.encode_png() {
+        Ok(png) => Ok(png),
+        Err(error) => Err(Error::SimpleError(format!("PNG encoding error: {}", error))),
+    }
+}
+
+#[cfg(not(feature = "png"))]
+pub fn render_png(_svg: &str) -> Result<Vec<u8>, Error> {
+    Err(Error::SimpleError(String::from(
+        "PNG export is not available, build with '--features png'",
+    )))
+}
+
+/// Writes a diagram of the game state to the given path. Paths ending in
+/// `.png` are written as PNG, all others as SVG.
+pub fn save_diagram<P: AsRef<Path>>(
+    game_state: &GameState,
+    options: &DiagramOptions,
+    path: P,
+) -> Result<(), Error> {
+    let svg = render_svg(game_state, options);
+    let is_png = path
+        .as_ref()
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("png"));
+    let contents = if is_png { render_png(&svg)? } else { svg.into_bytes() };
+
+    match fs::write(path, contents) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(Error::IOError(error)),
+    }
+}
@@ -3,9 +3,17 @@ use std::cmp::Ordering;
 use crate::util::coordinates::Coordinates;
 use crate::util::error::Error;
 use crate::xml::{conversion::FromDeserializable, enums::PlayerTeam, server::state::State};
+use crate::xml::enums::PieceType;
 
 use super::piece::Piece;
-use super::{board::Board, moves::Move};
+use super::{
+    board::Board,
+    movegen::LegalMoves,
+    moves::{AmberReason, AnnotatedMove, Move},
+};
+
+/// The turn after the last of the 30 rounds, the game ends at the latest here.
+pub const ROUND_LIMIT_TURN: u32 = 60;
 
 #[derive(Debug, Clone)]
 pub struct GameState {
@@ -18,6 +26,13 @@ pub struct GameState {
     pub ambers: (i32, i32),
 }
 
+/// The changes of a move made with `GameState::make_move`.
+#[derive(Debug, Clone)]
+pub struct MoveUndo {
+    annotated_move: AnnotatedMove,
+    last_move: Option<Move>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum GameStateResult {
     Player(PlayerTeam),
@@ -25,7 +40,30 @@ pub enum GameStateResult {
     Nothing,
 }
 
+/// Why a game is over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameEndReason {
+    /// A team had at least two ambers at the end of a round.
+    Ambers,
+    /// The last round has been played.
+    RoundLimit,
+    /// The team to move has no pieces left.
+    NoPieces,
+    /// The team to move has pieces, but none of them can move.
+    NoLegalMoves,
+}
+
 impl GameState {
+    pub fn new(board: Board) -> Self {
+        Self {
+            start_team: PlayerTeam::One,
+            board,
+            last_move: None,
+            turn: 0,
+            ambers: (0, 0),
+        }
+    }
+
     pub fn get_current_team(&self) -> PlayerTeam {
         self.start_team.next_n(self.turn)
     }
@@ -83,20 +121,42 @@ impl GameState {
         GameStateResult::Draw
     }
 
+    /// Returns why the game is over, or `None` while it is running.
+    pub fn get_end_reason(&self) -> Option<GameEndReason> {
+        let round_over = self.turn.is_multiple_of(2);
+        let amber_limit_reached = self.ambers.0 >= 2 || self.ambers.1 >= 2;
+        if round_over && amber_limit_reached {
+            return Some(GameEndReason::Ambers);
+        }
+        if self.turn >= ROUND_LIMIT_TURN {
+            return Some(GameEndReason::RoundLimit);
+        }
+
+        if self.legal_moves().next().is_none() {
+            let team = self.get_current_team();
+            let has_pieces = self.board.pieces.values().any(|piece| piece.team == team);
+            return Some(if has_pieces {
+                GameEndReason::NoLegalMoves
+            } else {
+                GameEndReason::NoPieces
+            });
+        }
+
+        None
+    }
+
+    /// Returns the result of the game. Once the game is over, the team with
+    /// more ambers wins, equal ambers are decided by the positions of the
+    /// light pieces.
     pub fn get_result(&self) -> GameStateResult {
-        if self.turn >= 59 {
-            return match self.ambers {
-                (1, 0) | (2, _) | (3, _) => GameStateResult::Player(PlayerTeam::One),
-                (0, 1) | (_, 2) | (_, 3) => GameStateResult::Player(PlayerTeam::Two),
-                (0, 0) | (1, 1) => self.evaluate_light_piece_positions(),
-                (_, _) => GameStateResult::Nothing,
-            };
+        if self.get_end_reason().is_none() {
+            return GameStateResult::Nothing;
         }
 
-        match self.ambers {
-            (2, _) | (3, _) => GameStateResult::Player(PlayerTeam::One),
-            (_, 2) | (_, 3) => GameStateResult::Player(PlayerTeam::Two),
-            (_, _) => GameStateResult::Nothing,
+        match self.ambers.0.cmp(&self.ambers.1) {
+            Ordering::Greater => GameStateResult::Player(PlayerTeam::One),
+            Ordering::Less => GameStateResult::Player(PlayerTeam::Two),
+            Ordering::Equal => self.evaluate_light_piece_positions(),
         }
     }
 
@@ -155,11 +215,59 @@ impl GameState {
         moves
     }
 
+    /// Lazily generates the legal moves of the team to move.
+    pub fn legal_moves(&self) -> LegalMoves<'_> {
+        LegalMoves::all(self)
+    }
+
+    /// Lazily generates the legal captures of the team to move.
+    pub fn legal_captures(&self) -> LegalMoves<'_> {
+        LegalMoves::captures(self)
+    }
+
+    /// Annotates a move with the moving and the captured piece and whether it
+    /// makes a stack or scores an amber. Doesn't check if the move is legal.
+    pub fn annotate_move(&self, r#move: &Move) -> Option<AnnotatedMove> {
+        let piece = self.board.get_piece_at(&r#move.from)?.clone();
+        let captured_piece = self.board.get_piece_at(&r#move.to).cloned();
+
+        let count = piece.count + captured_piece.as_ref().map_or(0, |captured| captured.count);
+        let amber = if count > 2 {
+            Some(AmberReason::Capture)
+        } else if piece.is_light_piece() && r#move.to.x == piece.team.opponent().start_line() {
+            Some(AmberReason::StartLine)
+        } else {
+            None
+        };
+        let makes_stack = captured_piece.is_some() && amber.is_none();
+
+        Some(AnnotatedMove {
+            r#move: r#move.clone(),
+            piece,
+            captured_piece,
+            makes_stack,
+            amber,
+        })
+    }
+
+    pub fn calculate_annotated_moves(&self, team: &PlayerTeam) -> Vec<AnnotatedMove> {
+        self.calculate_possible_moves(team)
+            .iter()
+            .filter_map(|r#move| self.annotate_move(r#move))
+            .collect()
+    }
+
     fn advance(&mut self) {
         self.turn += 1;
     }
 
     pub fn perform_move(&mut self, r#move: &Move) -> Result<(), Error> {
+        self.make_move(r#move).map(|_| ())
+    }
+
+    /// Performs the move like `perform_move` and returns what is needed to
+    /// take it back with `unmake_move`.
+    pub fn make_move(&mut self, r#move: &Move) -> Result<MoveUndo, Error> {
         let team = self.get_current_team();
         if !self.can_perform_move(r#move, team.clone()) {
             return Err(Error::SimpleError(String::from(
@@ -167,48 +275,34 @@ impl GameState {
             )));
         }
 
+        let annotated_move = match self.annotate_move(r#move) {
+            Some(annotated_move) => annotated_move,
+            None => return Err(Error::SimpleError(String::from("There is no piece to move."))),
+        };
         let move_from = r#move.from.clone();
         let move_to = r#move.to.clone();
 
-        if let Some(piece_at_target) = self.board.get_piece_at(&move_to) {
-            // If the piece at the target position is stacked:
-            // - remove it
-            // - remove own piece
-            // - increment ambers count for own team
-            if piece_at_target.is_stacked() {
-                self.board.pieces.remove(&move_to);
-                self.board.pieces.remove(&move_from);
-                self.increment_ambers_for(team);
-            }
-            // If the piece at the target position is not stacked:
-            // - remove it
-            // - move own piece to the target position
-            // - make own piece stacked
-            else {
-                self.board.pieces.remove(&move_to);
-                self.board.move_piece(&move_from, &move_to);
-
-                if let Some(moved_piece_at_target) = self.board.get_piece_at_ref_mut(&move_to) {
-                    moved_piece_at_target.count = 2;
-                }
-            }
+        // If the stack would get higher than two pieces, or a light piece
+        // reaches the opponent's start line:
+        // - remove the moved piece (and the captured pieces with it)
+        // - increment amber count for the team that performed the move
+        if annotated_move.scores_amber() {
+            self.board.pieces.remove(&move_to);
+            self.board.pieces.remove(&move_from);
+            self.increment_ambers_for(team);
         }
-	// Otherwise, if the move moves to the opponent's start line and the piece was a light piece:
-	// - remove the moved piece
-	// - increment amber count for the team that performed the move
-	else if move_to.x == team.opponent().start_line() {
-	    if let Some(moved_piece) = self.board.get_piece_at(&move_from) {
-		if moved_piece.is_light_piece() {
-		    self.board.pieces.remove(&move_from);
-		    self.increment_ambers_for(team);
-		}
-	    }
-	}
-        // If there is no piece at the target:
+        // Otherwise:
         // - move own piece to the target position
+        // - stack a captured piece onto it
         else {
-            // Update the position of the moved piece
+            self.board.pieces.remove(&move_to);
             self.board.move_piece(&move_from, &move_to);
+
+            if annotated_move.makes_stack {
+                if let Some(moved_piece) = self.board.get_piece_at_ref_mut(&move_to) {
+                    moved_piece.count = 2;
+                }
+            }
         }
 
         // Advance the GameState
@@ -216,9 +310,88 @@ impl GameState {
 
         // Set last move
         let cloned_move = r#move.clone();
-        self.last_move = Some(cloned_move);
+        let last_move = self.last_move.replace(cloned_move);
+
+        Ok(MoveUndo {
+            annotated_move,
+            last_move,
+        })
+    }
+
+    /// Takes back the last move made with `make_move`.
+    pub fn unmake_move(&mut self, undo: MoveUndo) {
+        self.turn -= 1;
+        self.last_move = undo.last_move;
+
+        let annotated_move = undo.annotated_move;
+        if annotated_move.scores_amber() {
+            match self.get_current_team() {
+                PlayerTeam::One => self.ambers.0 -= 1,
+                PlayerTeam::Two => self.ambers.1 -= 1,
+            }
+        }
+
+        let r#move = annotated_move.r#move;
+        self.board.pieces.remove(&r#move.to);
+        self.board.pieces.insert(r#move.from, annotated_move.piece);
+        if let Some(captured_piece) = annotated_move.captured_piece {
+            self.board.pieces.insert(r#move.to, captured_piece);
+        }
+    }
+}
+
+impl GameState {
+    /// Formats the game state the same way the server sends it inside a `memento`.
+    pub fn to_xml(&self) -> String {
+        let team_name = |team: &PlayerTeam| match team {
+            PlayerTeam::One => "ONE",
+            PlayerTeam::Two => "TWO",
+        };
+        let piece_type_name = |piece_type: &PieceType| match piece_type {
+            PieceType::Herzmuschel => "Herzmuschel",
+            PieceType::Moewe => "Moewe",
+            PieceType::Seestern => "Seestern",
+            PieceType::Robbe => "Robbe",
+        };
+
+        let mut entries = self.board.pieces.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|entry| (entry.0.x, entry.0.y));
+
+        let pieces_declaration = entries
+            .iter()
+            .map(|(coordinates, piece)| {
+                format!(
+                    "<entry><coordinates x=\"{}\" y=\"{}\"/><piece type=\"{}\" team=\"{}\" count=\"{}\"/></entry>",
+                    coordinates.x,
+                    coordinates.y,
+                    piece_type_name(&piece.piece_type),
+                    team_name(&piece.team),
+                    piece.count
+                )
+            })
+            .collect::<String>();
+
+        let last_move_declaration = match &self.last_move {
+            Some(last_move) => format!(
+                "<lastMove><from x=\"{}\" y=\"{}\"/><to x=\"{}\" y=\"{}\"/></lastMove>",
+                last_move.from.x, last_move.from.y, last_move.to.x, last_move.to.y
+            ),
+            None => String::new(),
+        };
 
-        Ok(())
+        let ambers_declaration = format!(
+            "<ambers enum-type=\"team\"><entry><team>ONE</team><int>{}</int></entry><entry><team>TWO</team><int>{}</int></entry></ambers>",
+            self.ambers.0, self.ambers.1
+        );
+
+        format!(
+            "<state class=\"state\" turn=\"{}\"><startTeam>{}</startTeam><board><pieces>{}</pieces></board>{}{}</state>",
+            self.turn,
+            team_name(&self.start_team),
+            pieces_declaration,
+            last_move_declaration,
+            ambers_declaration
+        )
     }
 }
 
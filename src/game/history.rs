@@ -0,0 +1,170 @@
+use crate::xml::enums::PlayerTeam;
+
+use super::game_state::{GameState, GameStateResult};
+use super::moves::Move;
+use super::notation::{format_move, format_position};
+use super::zobrist::position_hash;
+
+/// A game state of the history and the move that was played in it, once it
+/// is known.
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub game_state: GameState,
+    pub played_move: Option<Move>,
+}
+
+/// The game states of a game in order, together with the moves of both teams.
+#[derive(Debug, Clone, Default)]
+pub struct GameHistory {
+    entries: Vec<HistoryEntry>,
+}
+
+impl GameHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_game_states(game_states: &[GameState]) -> Self {
+        let mut history = Self::new();
+        for game_state in game_states.iter() {
+            history.push_state(game_state.clone());
+        }
+        history
+    }
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn current_state(&self) -> Option<&GameState> {
+        self.entries.last().map(|entry| &entry.game_state)
+    }
+
+    /// Appends a game state. If it directly follows the current game state,
+    /// its last move is attached to the current game state as the move
+    /// played there, replacing a move recorded before.
+    pub fn push_state(&mut self, game_state: GameState) {
+        if let (Some(previous), Some(last_move)) = (self.entries.last_mut(), &game_state.last_move) {
+            if previous.game_state.turn + 1 == game_state.turn {
+                previous.played_move = Some(last_move.clone());
+            }
+        }
+        self.entries.push(HistoryEntry {
+            game_state,
+            played_move: None,
+        });
+    }
+
+    /// Records the move played in the current game state, e.g. our own move
+    /// before the server confirms it with the next game state.
+    pub fn record_move(&mut self, r#move: Move) {
+        match self.entries.last_mut() {
+            Some(entry) => entry.played_move = Some(r#move),
+            None => log::warn!("Tried to record a move before any game state was added"),
+        }
+    }
+
+    /// The last move of the game, either recorded for the current game state
+    /// or the one that led to it.
+    pub fn last_move(&self) -> Option<&Move> {
+        let entry = self.entries.last()?;
+        entry.played_move.as_ref().or(entry.game_state.last_move.as_ref())
+    }
+
+    /// All known moves with the team that played them, in order.
+    pub fn moves(&self) -> Vec<(PlayerTeam, &Move)> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                entry
+                    .played_move
+                    .as_ref()
+                    .map(|played_move| (entry.game_state.get_current_team(), played_move))
+            })
+            .collect()
+    }
+
+    /// The moves played by the given team, e.g. to look at the opponent's
+    /// moves.
+    pub fn moves_of(&self, team: &PlayerTeam) -> Vec<&Move> {
+        self.moves()
+            .into_iter()
+            .filter(|(move_team, _)| move_team == team)
+            .map(|(_, played_move)| played_move)
+            .collect()
+    }
+
+    /// The position hashes of all game states, oldest first.
+    pub fn position_hashes(&self) -> Vec<u64> {
+        self.entries
+            .iter()
+            .map(|entry| position_hash(&entry.game_state))
+            .collect()
+    }
+
+    /// How often the position of the game state occurred in the history.
+    pub fn repetitions(&self, game_state: &GameState) -> usize {
+        let hash = position_hash(game_state);
+        self.position_hashes()
+            .into_iter()
+            .filter(|entry_hash| *entry_hash == hash)
+            .count()
+    }
+
+    /// Exports the game as text: the first position in notation, one line
+    /// per round with the moves of both teams and the result.
+    pub fn to_text(&self) -> String {
+        let first_state = match self.entries.first() {
+            Some(entry) => &entry.game_state,
+            None => return String::new(),
+        };
+
+        let mut text = format!("{}\n", format_position(first_state));
+        let mut lines = Vec::new();
+        let mut current_round = None;
+        for entry in self.entries.iter() {
+            let played_move = match &entry.played_move {
+                Some(played_move) => played_move,
+                None => continue,
+            };
+            let turn = entry.game_state.turn;
+            let round = turn / 2 + 1;
+            if current_round != Some(round) {
+                current_round = Some(round);
+                lines.push(if turn % 2 == 1 {
+                    format!("{}. ..", round)
+                } else {
+                    format!("{}.", round)
+                });
+            }
+            if let Some(line) = lines.last_mut() {
+                line.push(' ');
+                line.push_str(&format_move(played_move));
+            }
+        }
+        for line in lines {
+            text.push_str(&line);
+            text.push('\n');
+        }
+
+        if let Some(last_state) = self.current_state() {
+            let result = match last_state.get_result() {
+                GameStateResult::Player(PlayerTeam::One) => "ONE",
+                GameStateResult::Player(PlayerTeam::Two) => "TWO",
+                GameStateResult::Draw => "DRAW",
+                GameStateResult::Nothing => "-",
+            };
+            text.push_str(&format!(
+                "Final position: {}\nResult: {}\n",
+                format_position(last_state),
+                result
+            ));
+        }
+
+        text
+    }
+}
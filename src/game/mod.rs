@@ -1,5 +1,14 @@
+pub mod attacks;
 pub mod board;
+pub mod diagram;
 pub mod game_state;
+pub mod history;
+pub mod movegen;
 pub mod moves;
+pub mod notation;
+pub mod perft;
 pub mod piece;
+pub mod render;
+pub mod replay;
 pub mod result;
+pub mod zobrist;
@@ -0,0 +1,113 @@
+//! Lazy legal move generation for the team to move.
+
+use std::collections::hash_map::Iter as PiecesIter;
+
+use crate::util::coordinates::Coordinates;
+use crate::xml::enums::{PieceType, PlayerTeam};
+
+use super::board::Board;
+use super::game_state::GameState;
+use super::moves::Move;
+use super::piece::Piece;
+
+const HERZMUSCHEL_ONE: [(i32, i32); 2] = [(1, -1), (1, 1)];
+const HERZMUSCHEL_TWO: [(i32, i32); 2] = [(-1, -1), (-1, 1)];
+const MOEWE: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const SEESTERN_ONE: [(i32, i32); 5] = [(1, 0), (1, -1), (1, 1), (-1, -1), (-1, 1)];
+const SEESTERN_TWO: [(i32, i32); 5] = [(-1, 0), (1, -1), (1, 1), (-1, -1), (-1, 1)];
+const ROBBE: [(i32, i32); 8] = [
+    (-1, -2),
+    (1, -2),
+    (2, -1),
+    (2, 1),
+    (1, 2),
+    (-1, 2),
+    (-2, 1),
+    (-2, -1),
+];
+
+/// The offsets a piece can move by, the same as
+/// `PieceType::calculate_offsets` without allocating.
+pub fn piece_offsets(piece_type: &PieceType, team: &PlayerTeam) -> &'static [(i32, i32)] {
+    match (piece_type, team) {
+        (PieceType::Herzmuschel, PlayerTeam::One) => &HERZMUSCHEL_ONE,
+        (PieceType::Herzmuschel, PlayerTeam::Two) => &HERZMUSCHEL_TWO,
+        (PieceType::Moewe, _) => &MOEWE,
+        (PieceType::Seestern, PlayerTeam::One) => &SEESTERN_ONE,
+        (PieceType::Seestern, PlayerTeam::Two) => &SEESTERN_TWO,
+        (PieceType::Robbe, _) => &ROBBE,
+    }
+}
+
+/// An iterator over the legal moves of the team to move. Moves are generated
+/// one piece at a time while iterating.
+pub struct LegalMoves<'a> {
+    board: &'a Board,
+    team: PlayerTeam,
+    captures_only: bool,
+    pieces: PiecesIter<'a, Coordinates, Piece>,
+    current_piece: Option<(&'a Coordinates, &'static [(i32, i32)])>,
+}
+
+impl<'a> LegalMoves<'a> {
+    fn new(game_state: &'a GameState, captures_only: bool) -> Self {
+        Self {
+            board: &game_state.board,
+            team: game_state.get_current_team(),
+            captures_only,
+            pieces: game_state.board.pieces.iter(),
+            current_piece: None,
+        }
+    }
+
+    /// All legal moves of the team to move.
+    pub fn all(game_state: &'a GameState) -> Self {
+        Self::new(game_state, false)
+    }
+
+    /// Only the legal moves that capture an opponent piece.
+    pub fn captures(game_state: &'a GameState) -> Self {
+        Self::new(game_state, true)
+    }
+
+    fn next_piece(&mut self) -> Option<(&'a Coordinates, &'static [(i32, i32)])> {
+        let team = &self.team;
+        self.pieces
+            .by_ref()
+            .find(|(_, piece)| &piece.team == team)
+            .map(|(coordinates, piece)| (coordinates, piece_offsets(&piece.piece_type, team)))
+    }
+}
+
+impl Iterator for LegalMoves<'_> {
+    type Item = Move;
+
+    fn next(&mut self) -> Option<Move> {
+        loop {
+            let (from, offsets) = match self.current_piece {
+                Some(current_piece) => current_piece,
+                None => self.next_piece()?,
+            };
+
+            for (index, (dx, dy)) in offsets.iter().enumerate() {
+                let to = Coordinates::new(from.x + dx, from.y + dy);
+                if !to.in_bounds() {
+                    continue;
+                }
+                let is_legal = match self.board.get_piece_at(&to) {
+                    Some(piece_at_target) => piece_at_target.team != self.team,
+                    None => !self.captures_only,
+                };
+                if is_legal {
+                    // Continue with the remaining offsets of this piece next time
+                    self.current_piece = Some((from, &offsets[index + 1..]));
+                    return Some(Move {
+                        from: from.clone(),
+                        to,
+                    });
+                }
+            }
+            self.current_piece = None;
+        }
+    }
+}
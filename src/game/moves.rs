@@ -1,7 +1,40 @@
 use crate::util::coordinates::Coordinates;
 
+use super::piece::Piece;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Move {
     pub from: Coordinates,
     pub to: Coordinates,
 }
+
+/// Why a move scores an amber.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmberReason {
+    /// A light piece reached the opponent's start line.
+    StartLine,
+    /// The capture would have stacked more than two pieces.
+    Capture,
+}
+
+/// A move together with its effects in the position it is played in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotatedMove {
+    pub r#move: Move,
+    pub piece: Piece,
+    pub captured_piece: Option<Piece>,
+    /// The capture leaves a stack of two pieces on the target.
+    pub makes_stack: bool,
+    /// The moved piece is removed and its team scores an amber.
+    pub amber: Option<AmberReason>,
+}
+
+impl AnnotatedMove {
+    pub fn is_capture(&self) -> bool {
+        self.captured_piece.is_some()
+    }
+
+    pub fn scores_amber(&self) -> bool {
+        self.amber.is_some()
+    }
+}
@@ -0,0 +1,349 @@
+//! A compact text notation for positions, similar to FEN in chess.
+//!
+//! A position consists of up to five space-separated fields:
+//!
+//! 1. The board: eight rows (y = 0 to 7) separated by `/`. Each row lists the
+//!    fields from x = 0 to 7, using `H`, `M`, `S` and `R` for the pieces of
+//!    team one, lowercase letters for team two and digits for runs of empty
+//!    fields. A `*` after a piece marks it as stacked.
+//! 2. The start team (`1` or `2`).
+//! 3. The turn.
+//! 4. The ambers of both teams, e.g. `1:0`.
+//! 5. Optionally the last move, e.g. `a2-b3`.
+//!
+//! Moves are written as `a2-b3`, or annotated with the moving piece, `x` for
+//! captures, `*` for a capture that makes a stack and `+` for an amber, e.g.
+//! `R b3xc5+`.
+
+use std::collections::HashMap;
+
+use crate::util::coordinates::Coordinates;
+use crate::util::error::Error;
+use crate::xml::enums::{PieceType, PlayerTeam};
+
+use super::board::Board;
+use super::game_state::GameState;
+use super::moves::{AnnotatedMove, Move};
+use super::piece::Piece;
+
+pub const START_POSITION: &str = "R6r/H6s/M6m/S6h/H6s/M6m/S6h/R6r 1 0 0:0";
+
+fn notation_error(message: &str, text: &str) -> Error {
+    Error::SimpleError(format!("{}: '{}'", message, text))
+}
+
+pub fn format_square(coordinates: &Coordinates) -> String {
+    let file = (b'a' + coordinates.x as u8) as char;
+    format!("{}{}", file, coordinates.y + 1)
+}
+
+pub fn parse_square(text: &str) -> Result<Coordinates, Error> {
+    let mut chars = text.chars();
+    let file = chars
+        .next()
+        .ok_or_else(|| notation_error("Empty square", text))?;
+    let rank = chars
+        .as_str()
+        .parse::<i32>()
+        .map_err(|_| notation_error("Invalid rank", text))?;
+
+    let coordinates = Coordinates::new(file as i32 - 'a' as i32, rank - 1);
+    if !coordinates.in_bounds() {
+        return Err(notation_error("Square out of bounds", text));
+    }
+    Ok(coordinates)
+}
+
+pub fn format_move(r#move: &Move) -> String {
+    format!("{}-{}", format_square(&r#move.from), format_square(&r#move.to))
+}
+
+/// Formats a move with its annotations, e.g. `R b3xc5+`: the moving piece,
+/// `x` instead of `-` for captures, `*` if the capture makes a stack and `+`
+/// if the move scores an amber.
+pub fn format_annotated_move(annotated_move: &AnnotatedMove) -> String {
+    let r#move = &annotated_move.r#move;
+    let separator = if annotated_move.is_capture() { 'x' } else { '-' };
+    let mut text = format!(
+        "{} {}{}{}",
+        piece_char(&annotated_move.piece),
+        format_square(&r#move.from),
+        separator,
+        format_square(&r#move.to)
+    );
+    if annotated_move.makes_stack {
+        text.push('*');
+    }
+    if annotated_move.scores_amber() {
+        text.push('+');
+    }
+    text
+}
+
+/// Formats a move with its annotations in the position it is played in, or
+/// without them if there is no piece to move.
+pub fn format_move_in(game_state: &GameState, r#move: &Move) -> String {
+    match game_state.annotate_move(r#move) {
+        Some(annotated_move) => format_annotated_move(&annotated_move),
+        None => format_move(r#move),
+    }
+}
+
+/// Formats the moves of a line played from the given position, annotating
+/// every move in the position it is played in.
+pub fn format_variation(game_state: &GameState, moves: &[Move]) -> String {
+    let mut game_state = game_state.clone();
+    let mut notations = Vec::with_capacity(moves.len());
+    for r#move in moves.iter() {
+        notations.push(format_move_in(&game_state, r#move));
+        if game_state.perform_move(r#move).is_err() {
+            break;
+        }
+    }
+    for r#move in moves.iter().skip(notations.len()) {
+        notations.push(format_move(r#move));
+    }
+    notations.join(" ")
+}
+
+/// The parts of a move in either notation.
+struct MoveNotation {
+    r#move: Move,
+    piece: Option<Piece>,
+    capture: bool,
+    stack: bool,
+    amber: bool,
+}
+
+impl MoveNotation {
+    fn is_annotated(&self) -> bool {
+        self.piece.is_some() || self.capture || self.stack || self.amber
+    }
+}
+
+fn parse_move_notation(text: &str) -> Result<MoveNotation, Error> {
+    let (piece, squares) = match text.trim().split_once(' ') {
+        Some((piece, squares)) => {
+            let mut chars = piece.chars();
+            let piece = match (chars.next().and_then(parse_piece), chars.next()) {
+                (Some(piece), None) => piece,
+                _ => return Err(notation_error("Invalid piece", text)),
+            };
+            (Some(piece), squares.trim())
+        }
+        None => (None, text.trim()),
+    };
+
+    let amber = squares.ends_with('+');
+    let squares = squares.trim_end_matches('+');
+    let stack = squares.ends_with('*');
+    let squares = squares.trim_end_matches('*');
+
+    let (from, to, capture) = match (squares.split_once('-'), squares.split_once('x')) {
+        (Some((from, to)), None) => (from, to, false),
+        (None, Some((from, to))) => (from, to, true),
+        _ => return Err(notation_error("Invalid move", text)),
+    };
+
+    Ok(MoveNotation {
+        r#move: Move {
+            from: parse_square(from)?,
+            to: parse_square(to)?,
+        },
+        piece,
+        capture,
+        stack,
+        amber,
+    })
+}
+
+/// Parses a move like `a2-b3`. The annotations of `R b3xc5+` are accepted,
+/// but not checked.
+pub fn parse_move(text: &str) -> Result<Move, Error> {
+    parse_move_notation(text).map(|notation| notation.r#move)
+}
+
+/// Parses a move in the given position and annotates it. If the text carries
+/// annotations, they have to match the position.
+pub fn parse_annotated_move(text: &str, game_state: &GameState) -> Result<AnnotatedMove, Error> {
+    let notation = parse_move_notation(text)?;
+    let annotated_move = game_state
+        .annotate_move(&notation.r#move)
+        .ok_or_else(|| notation_error("No piece on the start square", text))?;
+
+    if notation.is_annotated() {
+        let piece_matches = notation
+            .piece
+            .is_none_or(|piece| piece.piece_type == annotated_move.piece.piece_type);
+        if !piece_matches
+            || notation.capture != annotated_move.is_capture()
+            || notation.stack != annotated_move.makes_stack
+            || notation.amber != annotated_move.scores_amber()
+        {
+            return Err(Error::SimpleError(format!(
+                "The move '{}' doesn't match the position, expected '{}'",
+                text,
+                format_annotated_move(&annotated_move)
+            )));
+        }
+    }
+
+    Ok(annotated_move)
+}
+
+fn piece_char(piece: &Piece) -> char {
+    let piece_char = match piece.piece_type {
+        PieceType::Herzmuschel => 'H',
+        PieceType::Moewe => 'M',
+        PieceType::Seestern => 'S',
+        PieceType::Robbe => 'R',
+    };
+    match piece.team {
+        PlayerTeam::One => piece_char,
+        PlayerTeam::Two => piece_char.to_ascii_lowercase(),
+    }
+}
+
+/// Formats a single piece like in the board field, e.g. `M*` or `s`.
+pub fn format_piece(piece: &Piece) -> String {
+    if piece.is_stacked() {
+        format!("{}*", piece_char(piece))
+    } else {
+        piece_char(piece).to_string()
+    }
+}
+
+fn parse_piece(piece_char: char) -> Option<Piece> {
+    let piece_type = match piece_char.to_ascii_uppercase() {
+        'H' => PieceType::Herzmuschel,
+        'M' => PieceType::Moewe,
+        'S' => PieceType::Seestern,
+        'R' => PieceType::Robbe,
+        _ => return None,
+    };
+    let team = if piece_char.is_ascii_uppercase() {
+        PlayerTeam::One
+    } else {
+        PlayerTeam::Two
+    };
+
+    Some(Piece {
+        piece_type,
+        team,
+        count: 1,
+    })
+}
+
+fn format_board(board: &Board) -> String {
+    let mut rows = Vec::with_capacity(8);
+    for y in 0..8 {
+        let mut row = String::new();
+        let mut empty_fields = 0;
+        for x in 0..8 {
+            match board.get_piece_at(&Coordinates::new(x, y)) {
+                Some(piece) => {
+                    if empty_fields > 0 {
+                        row.push_str(&empty_fields.to_string());
+                        empty_fields = 0;
+                    }
+                    row.push_str(&format_piece(piece));
+                }
+                None => empty_fields += 1,
+            }
+        }
+        if empty_fields > 0 {
+            row.push_str(&empty_fields.to_string());
+        }
+        rows.push(row);
+    }
+    rows.join("/")
+}
+
+fn parse_board(text: &str) -> Result<Board, Error> {
+    let rows = text.split('/').collect::<Vec<&str>>();
+    if rows.len() != 8 {
+        return Err(notation_error("The board needs exactly 8 rows", text));
+    }
+
+    let mut pieces: HashMap<Coordinates, Piece> = HashMap::new();
+    for (y, row) in rows.iter().enumerate() {
+        let mut x = 0;
+        let mut last_coordinates = None;
+        for row_char in row.chars() {
+            if let Some(empty_fields) = row_char.to_digit(10) {
+                x += empty_fields as i32;
+            } else if row_char == '*' {
+                let piece = last_coordinates
+                    .as_ref()
+                    .and_then(|coordinates| pieces.get_mut(coordinates))
+                    .ok_or_else(|| notation_error("Stack marker without a piece", row))?;
+                piece.count = 2;
+            } else {
+                let piece =
+                    parse_piece(row_char).ok_or_else(|| notation_error("Unknown piece", row))?;
+                let coordinates = Coordinates::new(x, y as i32);
+                pieces.insert(coordinates.clone(), piece);
+                last_coordinates = Some(coordinates);
+                x += 1;
+            }
+        }
+        if x != 8 {
+            return Err(notation_error("A row needs exactly 8 fields", row));
+        }
+    }
+
+    Ok(Board { pieces })
+}
+
+pub fn format_position(game_state: &GameState) -> String {
+    let start_team = match game_state.start_team {
+        PlayerTeam::One => 1,
+        PlayerTeam::Two => 2,
+    };
+    let mut position = format!(
+        "{} {} {} {}:{}",
+        format_board(&game_state.board),
+        start_team,
+        game_state.turn,
+        game_state.ambers.0,
+        game_state.ambers.1
+    );
+    if let Some(last_move) = &game_state.last_move {
+        position.push(' ');
+        position.push_str(&format_move(last_move));
+    }
+    position
+}
+
+pub fn parse_position(text: &str) -> Result<GameState, Error> {
+    let fields = text.split_whitespace().collect::<Vec<&str>>();
+    if fields.len() < 4 || fields.len() > 5 {
+        return Err(notation_error("A position needs 4 or 5 fields", text));
+    }
+
+    let board = parse_board(fields[0])?;
+    let start_team = match fields[1] {
+        "1" => PlayerTeam::One,
+        "2" => PlayerTeam::Two,
+        _ => return Err(notation_error("Invalid start team", fields[1])),
+    };
+    let turn = fields[2]
+        .parse::<u32>()
+        .map_err(|_| notation_error("Invalid turn", fields[2]))?;
+    let ambers = fields[3]
+        .split_once(':')
+        .and_then(|(one, two)| Some((one.parse::<i32>().ok()?, two.parse::<i32>().ok()?)))
+        .ok_or_else(|| notation_error("Invalid ambers", fields[3]))?;
+    let last_move = match fields.get(4) {
+        Some(last_move) => Some(parse_move(last_move)?),
+        None => None,
+    };
+
+    Ok(GameState {
+        start_team,
+        board,
+        last_move,
+        turn,
+        ambers,
+    })
+}
@@ -0,0 +1,22 @@
+use super::game_state::{GameState, GameStateResult};
+
+/// Counts the leaf nodes of the game tree up to the given depth. Positions in
+/// which the game is already decided are counted as leaves.
+pub fn perft(game_state: &GameState, depth: u32) -> u64 {
+    if depth == 0 || game_state.get_result() != GameStateResult::Nothing {
+        return 1;
+    }
+
+    if depth == 1 {
+        return game_state.legal_moves().count() as u64;
+    }
+
+    let mut nodes = 0;
+    for possible_move in game_state.legal_moves() {
+        let mut next_game_state = game_state.clone();
+        if next_game_state.perform_move(&possible_move).is_ok() {
+            nodes += perft(&next_game_state, depth - 1);
+        }
+    }
+    nodes
+}
@@ -0,0 +1,238 @@
+use std::env;
+use std::io::{self, IsTerminal};
+
+use crate::util::{coordinates::Coordinates, error::Error};
+use crate::xml::enums::{PieceType, PlayerTeam};
+
+use super::{board::Board, game_state::GameState, moves::Move, notation::format_move, piece::Piece};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const TEAM_ONE_COLOR: &str = "\x1b[1;31m";
+const TEAM_TWO_COLOR: &str = "\x1b[1;34m";
+const HIGHLIGHT_BACKGROUND: &str = "\x1b[43m";
+const CURSOR: &str = "\x1b[7m";
+
+pub const RENDER_STYLE_NAMES: [&str; 4] = ["auto", "color", "unicode", "ascii"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderStyle {
+    /// Unicode glyphs with ANSI colours for the teams and highlighted squares.
+    Color,
+    /// Unicode glyphs, teams are told apart by filled and hollow glyphs.
+    Unicode,
+    /// Plain ASCII in the style of the position notation, e.g. for log files.
+    Ascii,
+}
+
+impl RenderStyle {
+    /// Parses a style name. "auto" uses colours when stdout is a terminal and
+    /// the NO_COLOR environment variable is not set, plain ASCII otherwise.
+    pub fn from_name(name: &str) -> Result<Self, Error> {
+        match name {
+            "auto" => Ok(Self::detect()),
+            "color" => Ok(RenderStyle::Color),
+            "unicode" => Ok(RenderStyle::Unicode),
+            "ascii" => Ok(RenderStyle::Ascii),
+            _ => Err(Error::SimpleError(format!(
+                "Unknown board style '{}' (available: {})",
+                name,
+                RENDER_STYLE_NAMES.join(", ")
+            ))),
+        }
+    }
+
+    pub fn detect() -> Self {
+        if io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none() {
+            RenderStyle::Color
+        } else {
+            RenderStyle::Ascii
+        }
+    }
+}
+
+/// Renders boards and game states for the terminal. The board is drawn with
+/// y = 0 in the top row, like `impl Display for Board`, and labelled with the
+/// square names of the position notation.
+#[derive(Debug, Clone)]
+pub struct BoardRenderer {
+    pub style: RenderStyle,
+    /// Additional squares to highlight, e.g. the targets of a selected piece.
+    pub highlighted_squares: Vec<Coordinates>,
+    /// The square of the cursor of an interactive board.
+    pub cursor: Option<Coordinates>,
+}
+
+impl BoardRenderer {
+    pub fn new(style: RenderStyle) -> Self {
+        Self {
+            style,
+            highlighted_squares: Vec::new(),
+            cursor: None,
+        }
+    }
+
+    pub fn ascii() -> Self {
+        Self::new(RenderStyle::Ascii)
+    }
+
+    pub fn with_highlighted_squares(mut self, highlighted_squares: Vec<Coordinates>) -> Self {
+        self.highlighted_squares = highlighted_squares;
+        self
+    }
+
+    pub fn with_cursor(mut self, cursor: Option<Coordinates>) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    fn glyph(&self, piece: &Piece) -> char {
+        let team_one = piece.team == PlayerTeam::One;
+        match self.style {
+            RenderStyle::Ascii => {
+                let glyph = match piece.piece_type {
+                    PieceType::Herzmuschel => 'H',
+                    PieceType::Moewe => 'M',
+                    PieceType::Seestern => 'S',
+                    PieceType::Robbe => 'R',
+                };
+                if team_one {
+                    glyph
+                } else {
+                    glyph.to_ascii_lowercase()
+                }
+            }
+            RenderStyle::Color | RenderStyle::Unicode => match (&piece.piece_type, team_one) {
+                (PieceType::Herzmuschel, true) => '♥',
+                (PieceType::Herzmuschel, false) => '♡',
+                (PieceType::Moewe, true) => '▲',
+                (PieceType::Moewe, false) => '△',
+                (PieceType::Seestern, true) => '★',
+                (PieceType::Seestern, false) => '☆',
+                (PieceType::Robbe, true) => '●',
+                (PieceType::Robbe, false) => '○',
+            },
+        }
+    }
+
+    fn stack_marker(&self, piece: &Piece) -> char {
+        match (piece.is_stacked(), self.style) {
+            (false, _) => ' ',
+            (true, RenderStyle::Ascii) => '*',
+            (true, _) => '²',
+        }
+    }
+
+    fn render_square(&self, piece: Option<&Piece>, highlighted: bool, cursor: bool) -> String {
+        let (glyph, stack_marker) = match piece {
+            Some(piece) => (self.glyph(piece), self.stack_marker(piece)),
+            None if self.style == RenderStyle::Ascii => ('.', ' '),
+            None => ('·', ' '),
+        };
+
+        match self.style {
+            RenderStyle::Color => {
+                let color = match piece.map(|piece| &piece.team) {
+                    Some(PlayerTeam::One) => TEAM_ONE_COLOR,
+                    Some(PlayerTeam::Two) => TEAM_TWO_COLOR,
+                    None => DIM,
+                };
+                let background = if highlighted { HIGHLIGHT_BACKGROUND } else { "" };
+                let cursor = if cursor { CURSOR } else { "" };
+                format!("{}{}{} {}{} {}", background, cursor, color, glyph, stack_marker, RESET)
+            }
+            RenderStyle::Unicode | RenderStyle::Ascii => {
+                let (open, close) = match (cursor, highlighted) {
+                    (true, _) => ('<', '>'),
+                    (false, true) => ('[', ']'),
+                    (false, false) => (' ', ' '),
+                };
+                format!("{}{}{}{}", open, glyph, stack_marker, close)
+            }
+        }
+    }
+
+    fn file_labels(&self) -> String {
+        let mut labels = String::from("   ");
+        for x in 0..8u8 {
+            labels.push_str(&format!(" {}  ", (b'a' + x) as char));
+        }
+        labels.trim_end().to_string()
+    }
+
+    /// Renders the board with coordinate labels. The squares of the last move
+    /// and the additional highlighted squares are highlighted, the cursor is
+    /// drawn in reverse video or with angle brackets.
+    pub fn render_board(&self, board: &Board, last_move: Option<&Move>) -> String {
+        let mut output = String::new();
+        output.push_str(&self.file_labels());
+        output.push('\n');
+
+        for y in 0..8 {
+            output.push_str(&format!("{:>2} ", y + 1));
+            for x in 0..8 {
+                let coordinates = Coordinates::new(x, y);
+                let highlighted = self.highlighted_squares.contains(&coordinates)
+                    || last_move.is_some_and(|last_move| {
+                        last_move.from == coordinates || last_move.to == coordinates
+                    });
+                let cursor = self.cursor.as_ref() == Some(&coordinates);
+                output.push_str(&self.render_square(
+                    board.get_piece_at(&coordinates),
+                    highlighted,
+                    cursor,
+                ));
+            }
+            output.push_str(&format!(" {}\n", y + 1));
+        }
+
+        output.push_str(&self.file_labels());
+        output
+    }
+
+    fn team_name(&self, team: &PlayerTeam) -> String {
+        let name = match team {
+            PlayerTeam::One => "ONE",
+            PlayerTeam::Two => "TWO",
+        };
+        match (self.style, team) {
+            (RenderStyle::Color, PlayerTeam::One) => format!("{}{}{}", TEAM_ONE_COLOR, name, RESET),
+            (RenderStyle::Color, PlayerTeam::Two) => format!("{}{}{}", TEAM_TWO_COLOR, name, RESET),
+            _ => String::from(name),
+        }
+    }
+
+    /// Renders a line with the turn, the team to move, the ambers and the
+    /// last move of the game state.
+    pub fn render_status_line(&self, game_state: &GameState) -> String {
+        let turn = match self.style {
+            RenderStyle::Color => format!("{}Turn {}{}", BOLD, game_state.turn, RESET),
+            _ => format!("Turn {}", game_state.turn),
+        };
+        let last_move = match &game_state.last_move {
+            Some(last_move) => format_move(last_move),
+            None => String::from("-"),
+        };
+        format!(
+            "{} | {} to move | Ambers {} {} : {} {} | Last move {}",
+            turn,
+            self.team_name(&game_state.get_current_team()),
+            self.team_name(&PlayerTeam::One),
+            game_state.ambers.0,
+            game_state.ambers.1,
+            self.team_name(&PlayerTeam::Two),
+            last_move
+        )
+    }
+
+    /// Renders the status line followed by the board, highlighting the last
+    /// move of the game state.
+    pub fn render(&self, game_state: &GameState) -> String {
+        format!(
+            "{}\n{}",
+            self.render_status_line(game_state),
+            self.render_board(&game_state.board, game_state.last_move.as_ref())
+        )
+    }
+}
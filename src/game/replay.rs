@@ -0,0 +1,39 @@
+use std::fs;
+use std::path::Path;
+
+use crate::util::error::Error;
+use crate::xml::conversion::FromDeserializable;
+use crate::xml::enums::DataClass;
+use crate::xml::serialization::from_str;
+use crate::xml::server::replay::Replay;
+
+use super::game_state::GameState;
+
+pub fn parse_replay(text: &str) -> Result<Vec<GameState>, Error> {
+    let replay = from_str::<Replay>(text)?;
+
+    let mut game_states = Vec::with_capacity(replay.states.len() + replay.rooms.len());
+    let recorded_states = replay.turns.iter().map(|turn| &turn.state);
+    for state in replay.states.iter().chain(recorded_states) {
+        game_states.push(GameState::from_deserializable(state)?);
+    }
+
+    let memento_states = replay
+        .rooms
+        .iter()
+        .filter(|room| room.data.class == DataClass::Memento)
+        .filter_map(|room| room.data.state.as_ref());
+    for state in memento_states {
+        game_states.push(GameState::from_deserializable(state)?);
+    }
+
+    game_states.sort_by_key(|game_state| game_state.turn);
+    Ok(game_states)
+}
+
+pub fn load_replay<P: AsRef<Path>>(path: P) -> Result<Vec<GameState>, Error> {
+    match fs::read_to_string(path) {
+        Ok(text) => parse_replay(&text),
+        Err(error) => Err(Error::IOError(error)),
+    }
+}
@@ -0,0 +1,71 @@
+//! Zobrist hashing of positions. The hash covers the board, the team to move
+//! and the ambers, but not the turn, so that repeated positions get the same
+//! hash.
+
+use crate::xml::enums::{PieceType, PlayerTeam};
+
+use super::game_state::GameState;
+use super::piece::Piece;
+
+/// Piece types times teams times stacked or not.
+const PIECE_KINDS: usize = 16;
+const SQUARES: usize = 64;
+
+const TEAM_TWO_TO_MOVE_KEY: u64 = splitmix64(0x5EED_0000_0000_0001);
+const AMBERS_SEED: u64 = 0x5EED_0000_0000_0002;
+
+const PIECE_KEYS: [u64; SQUARES * PIECE_KINDS] = generate_piece_keys();
+
+const fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+const fn generate_piece_keys() -> [u64; SQUARES * PIECE_KINDS] {
+    let mut keys = [0; SQUARES * PIECE_KINDS];
+    let mut index = 0;
+    while index < keys.len() {
+        keys[index] = splitmix64(index as u64);
+        index += 1;
+    }
+    keys
+}
+
+fn piece_kind(piece: &Piece) -> usize {
+    let piece_type = match piece.piece_type {
+        PieceType::Herzmuschel => 0,
+        PieceType::Moewe => 1,
+        PieceType::Seestern => 2,
+        PieceType::Robbe => 3,
+    };
+    let team = match piece.team {
+        PlayerTeam::One => 0,
+        PlayerTeam::Two => 1,
+    };
+    piece_type * 4 + team * 2 + piece.is_stacked() as usize
+}
+
+/// The key of a piece on the given square, with `x` and `y` in `0..8`.
+pub fn piece_key(x: i32, y: i32, piece: &Piece) -> u64 {
+    let square = (y * 8 + x) as usize;
+    PIECE_KEYS[square * PIECE_KINDS + piece_kind(piece)]
+}
+
+/// Calculates the hash of a position from scratch.
+pub fn position_hash(game_state: &GameState) -> u64 {
+    let mut hash = game_state
+        .board
+        .pieces
+        .iter()
+        .fold(0, |hash, (coordinates, piece)| {
+            hash ^ piece_key(coordinates.x, coordinates.y, piece)
+        });
+
+    if game_state.get_current_team() == PlayerTeam::Two {
+        hash ^= TEAM_TWO_TO_MOVE_KEY;
+    }
+    let (ambers_one, ambers_two) = game_state.ambers;
+    hash ^ splitmix64(AMBERS_SEED ^ ((ambers_one as u64) << 32 | ambers_two as u64))
+}
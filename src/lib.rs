@@ -0,0 +1,17 @@
+//! Game model, rules, server protocol and strategies for the game
+//! Ostseeschach (Software-Challenge 2022).
+
+extern crate log;
+
+pub mod game;
+pub mod logic;
+mod networking;
+pub mod protocol;
+mod util;
+mod xml;
+
+pub use util::config;
+pub use util::coordinates::Coordinates;
+pub use util::error::Error;
+pub use util::logger_setup::setup_logger;
+pub use xml::enums::{PieceType, PlayerTeam};
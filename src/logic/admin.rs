@@ -0,0 +1,80 @@
+use std::env;
+use std::process::{Child, Command};
+
+use crate::util::error::Error;
+use crate::protocol::{manager::ProtocolManager, message::PreparedGame};
+
+/// Authenticates with the server password, prepares a game for the two
+/// players and returns the reservation codes.
+pub fn prepare_game(
+    host: &str,
+    port: i32,
+    password: &str,
+    player_names: (String, String),
+) -> Result<PreparedGame, Error> {
+    let mut protocol_manager = ProtocolManager::connect(String::from(host), port)?;
+    protocol_manager.prepare_game(password, player_names)?;
+    let prepared = protocol_manager.wait_for_prepared_response()?;
+    protocol_manager.close()?;
+    Ok(prepared)
+}
+
+pub fn print_reservations(prepared: &PreparedGame) {
+    println!("Prepared room: {}", prepared.room_id);
+    for (index, reservation) in prepared.reservations.iter().enumerate() {
+        println!("Reservation code for player {}: {}", index + 1, reservation);
+    }
+}
+
+fn launch_client(
+    host: &str,
+    port: i32,
+    global_args: &[String],
+    reservation: &str,
+) -> Result<Child, Error> {
+    let executable = match env::current_exe() {
+        Ok(executable) => executable,
+        Err(error) => return Err(Error::IOError(error)),
+    };
+
+    let spawn_result = Command::new(executable)
+        .args(global_args)
+        .arg("--host")
+        .arg(host)
+        .arg("--port")
+        .arg(port.to_string())
+        .arg("--reservation")
+        .arg(reservation)
+        .spawn();
+
+    match spawn_result {
+        Ok(child) => Ok(child),
+        Err(error) => Err(Error::IOError(error)),
+    }
+}
+
+/// Launches one local client per reservation and waits until all of them
+/// have terminated. The global options are put in front of the client
+/// arguments, so the clients read the same configuration file and log the
+/// same way as the launching process.
+pub fn launch_clients(
+    host: &str,
+    port: i32,
+    global_args: &[String],
+    prepared: &PreparedGame,
+) -> Result<(), Error> {
+    let mut children = Vec::new();
+    for reservation in prepared.reservations.iter() {
+        log::info!("Launching client with reservation code {}", reservation);
+        children.push(launch_client(host, port, global_args, reservation)?);
+    }
+
+    for mut child in children {
+        match child.wait() {
+            Ok(status) => log::info!("Client exited with {}", status),
+            Err(error) => return Err(Error::IOError(error)),
+        }
+    }
+
+    Ok(())
+}
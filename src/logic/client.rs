@@ -0,0 +1,70 @@
+use crate::protocol::manager::ProtocolManager;
+use crate::util::error::Error;
+
+use super::logic::{ClientState, Logic};
+use super::observer::Observer;
+
+fn close_connection(protocol_manager: &mut ProtocolManager) {
+    log::info!("Closing connection...");
+    if let Err(error) = protocol_manager.close() {
+        log::warn!("Error while trying to close the connection: {:?}", error);
+    }
+}
+
+/// Joins a game, with the reservation code if given, and lets the logic play
+/// it until the server ends the game.
+pub fn play_game(
+    host: &str,
+    port: i32,
+    reservation: Option<String>,
+    mut logic: Logic,
+) -> Result<(), Error> {
+    let mut protocol_manager = ProtocolManager::connect(String::from(host), port)?;
+    protocol_manager.join_game(reservation)?;
+
+    // Wait for a join response from the server
+    let room_id = protocol_manager.wait_for_joined_response()?;
+    log::info!("Joined game: {}", room_id);
+
+    log::info!("Starting game loop...");
+    loop {
+        let message = match protocol_manager.get_next_message() {
+            Ok(message) => message,
+            Err(error) => {
+                logic.save_recording();
+                return Err(error);
+            }
+        };
+        match logic.process_server_side_message(&mut protocol_manager, message) {
+            ClientState::Running => {}
+            ClientState::ShouldTerminate => {
+                close_connection(&mut protocol_manager);
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Observes the given room with the server password and passes every message
+/// to the observer until the server ends the game.
+pub fn observe_game(
+    host: &str,
+    port: i32,
+    password: &str,
+    mut observer: Observer,
+) -> Result<(), Error> {
+    let mut protocol_manager = ProtocolManager::connect(String::from(host), port)?;
+    protocol_manager.observe_game(&observer.room_id, password)?;
+
+    log::info!("Starting observer loop...");
+    loop {
+        let message = protocol_manager.get_next_message()?;
+        match observer.process_server_side_message(message) {
+            ClientState::Running => {}
+            ClientState::ShouldTerminate => {
+                close_connection(&mut protocol_manager);
+                return Ok(());
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::game::{
+    game_state::GameState,
+    moves::Move,
+    notation::{format_move, format_piece, format_position, format_square},
+    render::BoardRenderer,
+};
+use crate::util::coordinates::Coordinates;
+use crate::util::error::Error;
+
+use super::recording::room_file_stem;
+
+/// A received game state that differs from the one our rules predicted from
+/// the previous game state and the move the server reported.
+#[derive(Debug, Clone)]
+pub struct StateDivergence {
+    pub previous_state: GameState,
+    pub server_move: Move,
+    /// `None` if our rules rejected the move.
+    pub predicted_state: Option<GameState>,
+    pub received_state: GameState,
+    pub differences: Vec<String>,
+}
+
+/// Lists every difference in the board, the ambers and the turn between the
+/// predicted and the received game state.
+pub fn diff_game_states(predicted: &GameState, received: &GameState) -> Vec<String> {
+    let mut differences = Vec::new();
+
+    if predicted.turn != received.turn {
+        differences.push(format!(
+            "turn: predicted {}, received {}",
+            predicted.turn, received.turn
+        ));
+    }
+    if predicted.ambers != received.ambers {
+        differences.push(format!(
+            "ambers: predicted {}:{}, received {}:{}",
+            predicted.ambers.0, predicted.ambers.1, received.ambers.0, received.ambers.1
+        ));
+    }
+
+    let format_square_content = |game_state: &GameState, coordinates: &Coordinates| {
+        game_state
+            .board
+            .get_piece_at(coordinates)
+            .map(format_piece)
+            .unwrap_or_else(|| String::from("empty"))
+    };
+    for y in 0..8 {
+        for x in 0..8 {
+            let coordinates = Coordinates::new(x, y);
+            let predicted_content = format_square_content(predicted, &coordinates);
+            let received_content = format_square_content(received, &coordinates);
+            if predicted_content != received_content {
+                differences.push(format!(
+                    "{}: predicted {}, received {}",
+                    format_square(&coordinates),
+                    predicted_content,
+                    received_content
+                ));
+            }
+        }
+    }
+
+    differences
+}
+
+/// Applies the server's last move of the received game state to the previous
+/// game state and compares the result. Returns `None` if both agree, or if the
+/// states don't follow each other directly, so nothing can be predicted.
+pub fn cross_check(previous: &GameState, received: &GameState) -> Option<StateDivergence> {
+    let server_move = match received.last_move.clone() {
+        Some(server_move) => server_move,
+        None => {
+            log::debug!("Can't check the game state of turn {} without a last move", received.turn);
+            return None;
+        }
+    };
+    if received.turn != previous.turn + 1 {
+        log::debug!(
+            "Can't check the game state of turn {} against the one of turn {}",
+            received.turn,
+            previous.turn
+        );
+        return None;
+    }
+
+    let mut predicted = previous.clone();
+    let (predicted_state, differences) = match predicted.perform_move(&server_move) {
+        Ok(_) => {
+            let differences = diff_game_states(&predicted, received);
+            (Some(predicted), differences)
+        }
+        Err(error) => (
+            None,
+            vec![format!("our rules rejected the move: {}", error)],
+        ),
+    };
+    if differences.is_empty() {
+        return None;
+    }
+
+    Some(StateDivergence {
+        previous_state: previous.clone(),
+        server_move,
+        predicted_state,
+        received_state: received.clone(),
+        differences,
+    })
+}
+
+impl StateDivergence {
+    /// A bug report with the differences and all positions, both in notation
+    /// (for the rules fixtures) and as boards.
+    pub fn report(&self) -> String {
+        let renderer = BoardRenderer::ascii();
+        let mut report = format!(
+            "Rules divergence after {} in turn {}\n\nDifferences:\n",
+            format_move(&self.server_move),
+            self.previous_state.turn
+        );
+        for difference in self.differences.iter() {
+            report.push_str(&format!("- {}\n", difference));
+        }
+
+        let mut positions = vec![("Previous", &self.previous_state)];
+        if let Some(predicted_state) = &self.predicted_state {
+            positions.push(("Predicted", predicted_state));
+        }
+        positions.push(("Received", &self.received_state));
+        for (name, game_state) in positions {
+            report.push_str(&format!(
+                "\n{} position: {}\n{}\n",
+                name,
+                format_position(game_state),
+                renderer.render(game_state)
+            ));
+        }
+
+        report
+    }
+
+    /// Writes the report to `<directory>/<room id>-divergence-<turn>.txt`.
+    pub fn save(&self, directory: &Path, room_id: &str) -> Result<PathBuf, Error> {
+        let path = directory.join(format!(
+            "{}-divergence-{}.txt",
+            room_file_stem(room_id),
+            self.received_state.turn
+        ));
+        let write_result =
+            fs::create_dir_all(directory).and_then(|_| fs::write(&path, self.report()));
+
+        match write_result {
+            Ok(_) => Ok(path),
+            Err(error) => Err(Error::IOError(error)),
+        }
+    }
+}
+
+/// Cross-checks every pair of consecutive game states.
+pub fn cross_check_all(game_states: &[GameState]) -> Vec<StateDivergence> {
+    game_states
+        .windows(2)
+        .filter_map(|window| cross_check(&window[0], &window[1]))
+        .collect()
+}
@@ -0,0 +1,41 @@
+use crate::game::game_state::GameState;
+use crate::xml::enums::PlayerTeam;
+
+pub const AMBER_VALUE: i32 = 1000;
+pub const PIECE_VALUE: i32 = 100;
+pub const STACKED_BONUS: i32 = 50;
+pub const ADVANCEMENT_VALUE: i32 = 8;
+
+/// Evaluates a position from the point of view of team one: ambers, pieces
+/// and how far the light pieces have advanced towards the opponent's start
+/// line.
+pub fn evaluate_for_team_one(game_state: &GameState) -> i32 {
+    let mut score = AMBER_VALUE * (game_state.ambers.0 - game_state.ambers.1);
+
+    for (coordinates, piece) in game_state.board.pieces.iter() {
+        let mut piece_score = PIECE_VALUE;
+        if piece.is_stacked() {
+            piece_score += STACKED_BONUS;
+        }
+        if piece.is_light_piece() {
+            let advancement = (coordinates.x - piece.team.start_line()).abs();
+            piece_score += ADVANCEMENT_VALUE * advancement;
+        }
+
+        match piece.team {
+            PlayerTeam::One => score += piece_score,
+            PlayerTeam::Two => score -= piece_score,
+        }
+    }
+
+    score
+}
+
+/// Evaluates a position from the point of view of the team to move.
+pub fn evaluate(game_state: &GameState) -> i32 {
+    let score = evaluate_for_team_one(game_state);
+    match game_state.get_current_team() {
+        PlayerTeam::One => score,
+        PlayerTeam::Two => -score,
+    }
+}
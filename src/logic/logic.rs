@@ -1,20 +1,43 @@
+use std::path::PathBuf;
 use std::time::{Instant};
 
-use rand::{seq::SliceRandom, thread_rng};
-
-use crate::game::{game_state::GameState, moves::Move};
+use crate::game::{
+    game_state::{GameEndReason, GameState},
+    history::GameHistory,
+    moves::Move,
+    notation::format_move_in,
+    render::BoardRenderer,
+};
 use crate::protocol::{
     manager::ProtocolManager,
     message::{ClientSideMessage, ServerSideMessage},
 };
+use crate::util::error::Error;
 use crate::xml::enums::PlayerTeam;
 
+use super::crosscheck::{cross_check, StateDivergence};
+use super::recording::{room_file_stem, GameRecording, RecordedMove};
+use super::strategy::{RandomStrategy, Strategy};
+use super::telemetry::{MoveTelemetry, TelemetryWriter};
+
 pub struct Logic {
     pub current_game_state: Option<GameState>,
     pub room_id: Option<String>,
-    pub last_move: Option<Move>,
+    /// Every game state received from the server and the moves of both teams.
+    pub history: GameHistory,
 
     pub own_team: Option<PlayerTeam>,
+
+    /// Every received game state that differed from the one our rules
+    /// predicted.
+    pub divergences: Vec<StateDivergence>,
+
+    strategy: Box<dyn Strategy>,
+
+    recording_directory: Option<PathBuf>,
+    recording: Option<GameRecording>,
+
+    telemetry_writer: Option<TelemetryWriter>,
 }
 
 pub enum ClientState {
@@ -22,35 +45,182 @@ pub enum ClientState {
     ShouldTerminate,
 }
 
+/// What to answer a move request with.
+enum MoveDecision {
+    Send(Move),
+    /// By the rules the game is over, so there is no move to send.
+    GameOver(GameEndReason),
+    /// There is no game state or no own team to calculate a move for yet.
+    NotReady,
+}
+
+impl Default for Logic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Logic {
     pub fn new() -> Self {
+        Self::with_strategy(Box::new(RandomStrategy))
+    }
+
+    pub fn with_strategy(strategy: Box<dyn Strategy>) -> Self {
         Self {
             current_game_state: None,
             room_id: None,
-            last_move: None,
+            history: GameHistory::new(),
             own_team: None,
+            divergences: Vec::new(),
+            strategy,
+            recording_directory: None,
+            recording: None,
+            telemetry_writer: None,
         }
     }
 
-    fn calculate_move(&mut self) -> Option<Move> {
-        let game_state = self.current_game_state.as_mut()?;
-        let team = self.own_team.as_ref()?;
+    /// Records every game state and every sent move into a file named after
+    /// the room id inside the given directory. The file is written once the
+    /// game is over or the client stops because of an error.
+    pub fn enable_recording(&mut self, directory: PathBuf) {
+        self.recording_directory = Some(directory);
+    }
+
+    /// Appends a JSON record with the search statistics of every calculated
+    /// move to the given file.
+    pub fn enable_telemetry(&mut self, path: PathBuf) -> Result<(), Error> {
+        self.telemetry_writer = Some(TelemetryWriter::open(path)?);
+        Ok(())
+    }
+
+    /// Writes the recording of the current game, at most once per game. Called
+    /// when the game is over and when the client stops because of an error.
+    pub fn save_recording(&mut self) {
+        if let (Some(directory), Some(recording)) = (&self.recording_directory, self.recording.take()) {
+            if let Err(error) = recording.save(directory) {
+                log::warn!("Error while trying to save the game recording: {:?}", error);
+            }
+        }
+    }
+
+    /// Checks the received game state against the one predicted from the
+    /// previous memento and the server's last move. Divergences are logged and
+    /// saved as a report next to the recordings.
+    fn cross_check_memento(&mut self, game_state: &GameState) {
+        let previous = match self.history.current_state() {
+            Some(previous) => previous,
+            None => return,
+        };
+        let divergence = match cross_check(previous, game_state) {
+            Some(divergence) => divergence,
+            None => return,
+        };
+
+        log::error!("The server state differs from our rules:\n{}", divergence.report());
+        if let Some(directory) = &self.recording_directory {
+            let room_id = self.room_id.as_deref().unwrap_or("unknown");
+            match divergence.save(directory, room_id) {
+                Ok(path) => log::error!("Saved the rules bug report to {}", path.display()),
+                Err(error) => {
+                    log::warn!("Error while trying to save the rules bug report: {:?}", error)
+                }
+            }
+        }
+        self.divergences.push(divergence);
+    }
+
+    /// Returns the last move of the received game state if the opponent
+    /// played it.
+    fn opponent_move(&self, game_state: &GameState) -> Option<Move> {
+        let previous = self.history.current_state()?;
+        let own_team = self.own_team.as_ref()?;
+        if previous.turn + 1 != game_state.turn || &previous.get_current_team() == own_team {
+            return None;
+        }
+        game_state.last_move.clone()
+    }
+
+    /// Writes the history as text to `<directory>/<room id>.txt` next to the
+    /// recordings.
+    fn save_history(&self) {
+        let (directory, room_id) = match (&self.recording_directory, &self.room_id) {
+            (Some(directory), Some(room_id)) => (directory, room_id),
+            _ => return,
+        };
+        let path = directory.join(format!("{}.txt", room_file_stem(room_id)));
+        let write_result =
+            std::fs::create_dir_all(directory).and_then(|_| std::fs::write(&path, self.history.to_text()));
+        if let Err(error) = write_result {
+            log::warn!("Error while trying to save the game history: {:?}", error);
+        }
+    }
+
+    fn calculate_move(&mut self) -> MoveDecision {
+        let game_state = match (self.current_game_state.as_mut(), &self.own_team) {
+            (Some(game_state), Some(_)) => game_state,
+            _ => return MoveDecision::NotReady,
+        };
 
         log::info!("Current turn: {}", game_state.turn);
         log::info!("Current player: {:?}", game_state.get_current_team());
         log::info!("Current ambers: {:?}", game_state.ambers);
 
-        let start_time = Instant::now();
-        let possible_moves = game_state.calculate_possible_moves(&team);
-        let mut rng = thread_rng();
-        let sent_move = possible_moves.choose(&mut rng);
+        // Without a legal move there is nothing we could send. By the rules
+        // the game is over, so the server shouldn't have asked for a move
+        if let Some(reason @ (GameEndReason::NoPieces | GameEndReason::NoLegalMoves)) =
+            game_state.get_end_reason()
+        {
+            log::error!(
+                "Received a move request without a legal move ({:?}):\n{}",
+                reason,
+                BoardRenderer::ascii().render(game_state)
+            );
+            return MoveDecision::GameOver(reason);
+        }
 
-        let cloned_sent_move = sent_move.cloned()?;
+        let start_time = Instant::now();
+        let strategy_move = self
+            .strategy
+            .calculate_move_with_history(game_state, &self.history);
+        let cloned_sent_move = match strategy_move {
+            Some(strategy_move) => strategy_move,
+            None => {
+                // Sending nothing would be a timeout, so any legal move is better
+                log::warn!("The strategy found no move, sending the first legal move instead");
+                match game_state.legal_moves().next() {
+                    Some(legal_move) => legal_move,
+                    None => return MoveDecision::GameOver(GameEndReason::NoLegalMoves),
+                }
+            }
+        };
 
         let elapsed = start_time.elapsed();
-        log::info!("Calculated move: {:?}", cloned_sent_move);
+        log::info!("Calculated move: {}", format_move_in(game_state, &cloned_sent_move));
         log::info!("Needed {:?} to calculate move", elapsed);
 
+        if let Some(telemetry_writer) = self.telemetry_writer.as_mut() {
+            let legal_moves = game_state.legal_moves().count();
+            let telemetry = MoveTelemetry::new(
+                self.room_id.clone(),
+                game_state,
+                legal_moves,
+                &cloned_sent_move,
+                elapsed,
+                self.strategy.search_info().as_ref(),
+            );
+            if let Err(error) = telemetry_writer.write(&telemetry) {
+                log::warn!("Error while trying to write the search telemetry: {:?}", error);
+            }
+        }
+
+        if let Some(recording) = self.recording.as_mut() {
+            recording.record_move(RecordedMove {
+                sent_move: cloned_sent_move.clone(),
+                time_used: elapsed,
+                search_info: self.strategy.search_info(),
+            });
+        }
+
         match game_state.perform_move(&cloned_sent_move) {
             Ok(_) => {},
             Err(error) => {
@@ -58,30 +228,49 @@ impl Logic {
             },
         }
 
-        log::info!("New Game State: \n{}", game_state.board);
-        log::info!("Result: {:?}", game_state.get_result());
+        log::debug!("New Game State: \n{}", BoardRenderer::ascii().render(game_state));
+        match game_state.get_end_reason() {
+            Some(reason) => log::info!("Result: {:?} ({:?})", game_state.get_result(), reason),
+            None => log::info!("Result: {:?}", game_state.get_result()),
+        }
 
-        Some(cloned_sent_move)
+        MoveDecision::Send(cloned_sent_move)
     }
 
     fn process_move_request(&mut self, protocol_manager: &mut ProtocolManager) -> ClientState {
-        let calculated_move = self.calculate_move();
-        if let Some(sent_move) = calculated_move {
-            let state_room_id = self.room_id.as_ref().unwrap();
-            let room_id = String::from(state_room_id);
+        match self.calculate_move() {
+            MoveDecision::Send(sent_move) => {
+                let state_room_id = self.room_id.as_ref().unwrap();
+                let room_id = String::from(state_room_id);
 
-            let message = ClientSideMessage::Move { sent_move, room_id };
+                self.history.record_move(sent_move.clone());
+                let message = ClientSideMessage::Move { sent_move, room_id };
 
-            if let Err(error) = protocol_manager.send_client_side_message(message) {
-                log::error!("Error while trying to send move: {:?}", error);
-                return ClientState::ShouldTerminate;
+                if let Err(error) = protocol_manager.send_client_side_message(message) {
+                    log::error!("Error while trying to send move: {:?}", error);
+                    self.save_recording();
+                    return ClientState::ShouldTerminate;
+                }
+                ClientState::Running
+            }
+            MoveDecision::GameOver(reason) => {
+                // Ending the game here is better than letting the server
+                // wait for a move that can't be sent
+                if let Some(game_state) = self.current_game_state.as_ref() {
+                    log::info!("Result: {:?} ({:?})", game_state.get_result(), reason);
+                }
+                self.save_recording();
+                self.save_history();
+                ClientState::ShouldTerminate
+            }
+            MoveDecision::NotReady => {
+                log::warn!("Received a move request before the game state or the own team");
+                ClientState::Running
             }
         }
-
-        ClientState::Running
     }
 
-    pub fn process_server_side_message(
+    pub(crate) fn process_server_side_message(
         &mut self,
         protocol_manager: &mut ProtocolManager,
         message: ServerSideMessage,
@@ -89,13 +278,26 @@ impl Logic {
         match message {
             ServerSideMessage::Left => {
                 log::info!("Left");
+                self.save_recording();
                 ClientState::ShouldTerminate
             },
-            ServerSideMessage::MoveRequest => {
-                self.process_move_request(protocol_manager);
+            ServerSideMessage::Observed { room_id } => {
+                log::warn!("Unexpected observe confirmation for room {}", room_id);
                 ClientState::Running
             }
+            ServerSideMessage::MoveRequest => self.process_move_request(protocol_manager),
             ServerSideMessage::Memento { game_state } => {
+                if let Some(recording) = self.recording.as_mut() {
+                    recording.record_game_state(game_state.clone());
+                }
+
+                self.cross_check_memento(&game_state);
+                if let (Some(opponent_move), Some(previous)) =
+                    (self.opponent_move(&game_state), self.history.current_state())
+                {
+                    log::info!("Opponent played {}", format_move_in(previous, &opponent_move));
+                }
+                self.history.push_state(game_state.clone());
                 self.current_game_state = Some(game_state);
                 ClientState::Running
             }
@@ -107,18 +309,46 @@ impl Logic {
                 } else {
                     log::info!("Lost the game :(");
                 }
+                self.save_recording();
+                self.save_history();
 
                 ClientState::Running
             }
             ServerSideMessage::WelcomeMessage { room_id, own_team } => {
+                self.history = GameHistory::new();
+                if self.recording_directory.is_some() {
+                    self.recording = Some(GameRecording::new(room_id.clone(), own_team.clone()));
+                }
+
                 self.room_id = Some(room_id);
                 self.own_team = own_team;
 
                 log::info!("Own team: {:?}", self.own_team);
                 ClientState::Running
             }
-            ServerSideMessage::Error => {
-                log::error!("Received error message from server!");
+            ServerSideMessage::Error {
+                message,
+                original_move,
+            } => {
+                log::error!("Received error message from server: {}", message);
+                self.save_recording();
+
+                match original_move {
+                    Some(original_move) if self.history.last_move() == Some(&original_move) => {
+                        log::error!("The error was caused by our move: {:?}", original_move);
+                        if let Some(game_state) = self.current_game_state.as_ref() {
+                            log::error!(
+                                "Game state after the move: \n{}",
+                                BoardRenderer::ascii().render(game_state)
+                            );
+                        }
+                    }
+                    Some(original_move) => {
+                        log::error!("The error was caused by the move: {:?}", original_move);
+                    }
+                    None => {}
+                }
+
                 ClientState::ShouldTerminate
             }
         }
@@ -1 +1,18 @@
-pub mod logic;
\ No newline at end of file
+pub mod admin;
+pub mod client;
+pub mod crosscheck;
+pub mod evaluation;
+#[allow(clippy::module_inception)]
+pub mod logic;
+pub mod observer;
+pub mod proof;
+pub mod recording;
+pub mod replay;
+pub mod search;
+pub mod see;
+pub mod selfplay;
+pub mod session;
+pub mod solver;
+pub mod strategy;
+pub mod telemetry;
+pub mod viewer;
@@ -0,0 +1,43 @@
+use crate::game::render::BoardRenderer;
+use crate::protocol::message::ServerSideMessage;
+
+use super::logic::ClientState;
+
+pub struct Observer {
+    pub room_id: String,
+    pub renderer: BoardRenderer,
+}
+
+impl Observer {
+    pub fn new(room_id: String, renderer: BoardRenderer) -> Self {
+        Self { room_id, renderer }
+    }
+
+    pub fn process_server_side_message(&mut self, message: ServerSideMessage) -> ClientState {
+        match message {
+            ServerSideMessage::Observed { room_id } => {
+                log::info!("Observing room: {}", room_id);
+                ClientState::Running
+            }
+            ServerSideMessage::Memento { game_state } => {
+                println!("{}\n", self.renderer.render(&game_state));
+                ClientState::Running
+            }
+            ServerSideMessage::Result { result } => {
+                println!("Result of room {}: {:?}", self.room_id, result);
+                ClientState::Running
+            }
+            ServerSideMessage::Error { message, .. } => {
+                log::error!("Received error message from server: {}", message);
+                ClientState::ShouldTerminate
+            }
+            ServerSideMessage::Left => {
+                log::info!("Left");
+                ClientState::ShouldTerminate
+            }
+            ServerSideMessage::WelcomeMessage { .. } | ServerSideMessage::MoveRequest => {
+                ClientState::Running
+            }
+        }
+    }
+}
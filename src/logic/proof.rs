@@ -0,0 +1,323 @@
+//! Depth-first proof-number search (df-pn), which proves the result of a
+//! position with perfect play from both teams.
+//!
+//! Every node stores two numbers from the view of the team to move: `phi`,
+//! the number of leaves that still have to be proven for the team to reach
+//! its goal, and `delta`, the same for the opponent. A pass proves a single
+//! goal (a win, or at least a draw), so the result of a position takes up to
+//! two passes. The turn is part of the table key, which makes the game tree
+//! free of cycles.
+
+use std::collections::HashMap;
+use std::mem;
+use std::time::{Duration, Instant};
+
+use crate::game::{
+    game_state::{GameState, GameStateResult},
+    moves::Move,
+    zobrist::position_hash,
+};
+use crate::xml::enums::PlayerTeam;
+
+const INFINITY: u32 = u32::MAX / 2;
+
+/// How often (in nodes) the time limit is checked.
+const TIME_CHECK_INTERVAL: u64 = 1024;
+
+pub const DEFAULT_MEMORY_MB: usize = 16;
+
+/// The position hash, the turn and whether a draw reaches the goal.
+type ProofKey = (u64, u32, bool);
+
+#[derive(Debug, Clone, Copy)]
+struct ProofEntry {
+    phi: u32,
+    delta: u32,
+    /// The number of nodes spent on the entry, which decides what is kept
+    /// when the table is full.
+    work: u64,
+}
+
+impl ProofEntry {
+    fn is_solved(&self) -> bool {
+        self.phi == 0 || self.delta == 0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProofLimits {
+    /// The memory for the table of proof and disproof numbers.
+    pub memory_mb: usize,
+    pub move_time: Option<Duration>,
+    pub max_nodes: Option<u64>,
+}
+
+impl Default for ProofLimits {
+    fn default() -> Self {
+        Self {
+            memory_mb: DEFAULT_MEMORY_MB,
+            move_time: None,
+            max_nodes: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofResult {
+    /// The team to move wins by playing the move.
+    Win(Move),
+    /// The team to move can't win, but holds the draw by playing the move.
+    Draw(Move),
+    /// The team to move loses against every defence.
+    Loss,
+    /// A limit was reached before the result was proven, or the game is over.
+    Unknown,
+}
+
+/// A proof-number search that keeps its table between calls, so positions
+/// that were solved once are solved right away later in the game.
+pub struct ProofSearch {
+    limits: ProofLimits,
+    table: HashMap<ProofKey, ProofEntry>,
+    capacity: usize,
+    /// The team the goals are proven for, which is the team to move at the root.
+    attacker: Option<PlayerTeam>,
+    /// Whether a draw reaches the goal in the current pass.
+    draw_is_goal: bool,
+    start_time: Instant,
+    nodes: u64,
+    aborted: bool,
+}
+
+impl ProofSearch {
+    pub fn new(limits: ProofLimits) -> Self {
+        let entry_size = mem::size_of::<(ProofKey, ProofEntry)>();
+        let capacity = (limits.memory_mb * 1024 * 1024 / entry_size).max(1);
+        Self {
+            limits,
+            table: HashMap::new(),
+            capacity,
+            attacker: None,
+            draw_is_goal: false,
+            start_time: Instant::now(),
+            nodes: 0,
+            aborted: false,
+        }
+    }
+
+    pub fn set_move_time(&mut self, move_time: Option<Duration>) {
+        self.limits.move_time = move_time;
+    }
+
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    pub fn table_size(&self) -> usize {
+        self.table.len()
+    }
+
+    fn count_node(&mut self) {
+        self.nodes += 1;
+        if self
+            .limits
+            .max_nodes
+            .is_some_and(|max_nodes| self.nodes >= max_nodes)
+        {
+            self.aborted = true;
+        }
+        if self.nodes.is_multiple_of(TIME_CHECK_INTERVAL)
+            && self
+                .limits
+                .move_time
+                .is_some_and(|move_time| self.start_time.elapsed() >= move_time)
+        {
+            self.aborted = true;
+        }
+    }
+
+    fn key(&self, game_state: &GameState) -> ProofKey {
+        (
+            position_hash(game_state),
+            game_state.turn,
+            self.draw_is_goal,
+        )
+    }
+
+    /// The numbers of a finished game, from the view of the team to move.
+    fn terminal_numbers(&self, game_state: &GameState) -> Option<(u32, u32)> {
+        let goal_reached = match game_state.get_result() {
+            GameStateResult::Nothing => return None,
+            GameStateResult::Player(winner) => Some(winner) == self.attacker,
+            GameStateResult::Draw => self.draw_is_goal,
+        };
+        let attacker_to_move = Some(game_state.get_current_team()) == self.attacker;
+        if goal_reached == attacker_to_move {
+            Some((0, INFINITY))
+        } else {
+            Some((INFINITY, 0))
+        }
+    }
+
+    fn numbers(&self, game_state: &GameState) -> (u32, u32) {
+        self.terminal_numbers(game_state).unwrap_or_else(|| {
+            self.table
+                .get(&self.key(game_state))
+                .map_or((1, 1), |entry| (entry.phi, entry.delta))
+        })
+    }
+
+    fn child_numbers(&self, game_state: &mut GameState, r#move: &Move) -> (u32, u32) {
+        match game_state.make_move(r#move) {
+            Ok(undo) => {
+                let numbers = self.numbers(game_state);
+                game_state.unmake_move(undo);
+                numbers
+            }
+            // Illegal moves are never chosen
+            Err(_) => (INFINITY, 0),
+        }
+    }
+
+    fn store(&mut self, key: ProofKey, entry: ProofEntry) {
+        if self.table.len() >= self.capacity && !self.table.contains_key(&key) {
+            self.collect_garbage();
+        }
+        self.table.insert(key, entry);
+    }
+
+    /// Removes the unsolved entries with less than the median work. Solved
+    /// entries are kept, so the search stops once they fill the table.
+    fn collect_garbage(&mut self) {
+        let mut work = self
+            .table
+            .values()
+            .filter(|entry| !entry.is_solved())
+            .map(|entry| entry.work)
+            .collect::<Vec<u64>>();
+        if work.is_empty() {
+            self.aborted = true;
+            return;
+        }
+        let median = work.len() / 2;
+        let (_, &mut threshold, _) = work.select_nth_unstable(median);
+        self.table
+            .retain(|_, entry| entry.is_solved() || entry.work > threshold);
+        if self.table.len() >= self.capacity {
+            self.aborted = true;
+        }
+    }
+
+    /// Expands the node until its numbers reach one of the thresholds.
+    fn mid(&mut self, game_state: &mut GameState, phi_threshold: u32, delta_threshold: u32) {
+        self.count_node();
+        let key = self.key(game_state);
+        if let Some((phi, delta)) = self.terminal_numbers(game_state) {
+            self.store(
+                key,
+                ProofEntry {
+                    phi,
+                    delta,
+                    work: 1,
+                },
+            );
+            return;
+        }
+
+        let moves = game_state.legal_moves().collect::<Vec<Move>>();
+        let start_nodes = self.nodes;
+        loop {
+            // The team to move needs one child that is lost for the opponent
+            // and the opponent needs all of them
+            let mut delta = 0;
+            let mut best_child = 0;
+            let mut best_phi = INFINITY;
+            let mut best_delta = INFINITY;
+            let mut second_delta = INFINITY;
+            for (index, r#move) in moves.iter().enumerate() {
+                let (child_phi, child_delta) = self.child_numbers(game_state, r#move);
+                delta = (delta + child_phi).min(INFINITY);
+                if child_delta < best_delta {
+                    second_delta = best_delta;
+                    best_child = index;
+                    best_phi = child_phi;
+                    best_delta = child_delta;
+                } else if child_delta < second_delta {
+                    second_delta = child_delta;
+                }
+            }
+            let phi = best_delta;
+
+            if phi >= phi_threshold || delta >= delta_threshold || self.aborted {
+                let work = self.nodes - start_nodes + 1;
+                self.store(key, ProofEntry { phi, delta, work });
+                return;
+            }
+
+            let child_phi_threshold = delta_threshold - (delta - best_phi);
+            let child_delta_threshold = phi_threshold.min(second_delta + 1);
+            let undo = match game_state.make_move(&moves[best_child]) {
+                Ok(undo) => undo,
+                Err(_) => return,
+            };
+            self.mid(game_state, child_phi_threshold, child_delta_threshold);
+            game_state.unmake_move(undo);
+        }
+    }
+
+    /// Proves or disproves the goal of the current pass for the root, or
+    /// returns `None` if a limit was reached first.
+    fn prove(&mut self, game_state: &mut GameState) -> Option<bool> {
+        self.mid(game_state, INFINITY, INFINITY);
+        match self.numbers(game_state) {
+            (0, _) => Some(true),
+            (_, 0) => Some(false),
+            _ => None,
+        }
+    }
+
+    /// The move to a child that is lost for the opponent.
+    fn proving_move(&self, game_state: &mut GameState) -> Option<Move> {
+        let moves = game_state.legal_moves().collect::<Vec<Move>>();
+        moves
+            .into_iter()
+            .find(|r#move| self.child_numbers(game_state, r#move).1 == 0)
+    }
+
+    /// Proves whether the team to move wins, draws or loses.
+    pub fn solve(&mut self, game_state: &GameState) -> ProofResult {
+        self.start_time = Instant::now();
+        self.nodes = 0;
+        self.aborted = false;
+
+        if game_state.get_result() != GameStateResult::Nothing {
+            return ProofResult::Unknown;
+        }
+        let team = game_state.get_current_team();
+        if self.attacker.as_ref() != Some(&team) {
+            self.table.clear();
+            self.attacker = Some(team);
+        }
+        let mut game_state = game_state.clone();
+
+        self.draw_is_goal = false;
+        match self.prove(&mut game_state) {
+            Some(true) => {
+                return self
+                    .proving_move(&mut game_state)
+                    .map_or(ProofResult::Unknown, ProofResult::Win)
+            }
+            Some(false) => {}
+            None => return ProofResult::Unknown,
+        }
+
+        self.draw_is_goal = true;
+        match self.prove(&mut game_state) {
+            Some(true) => self
+                .proving_move(&mut game_state)
+                .map_or(ProofResult::Unknown, ProofResult::Draw),
+            Some(false) => ProofResult::Loss,
+            None => ProofResult::Unknown,
+        }
+    }
+}
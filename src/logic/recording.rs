@@ -0,0 +1,237 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::game::{game_state::GameState, moves::Move};
+use crate::util::coordinates::Coordinates;
+use crate::util::error::Error;
+use crate::xml::conversion::FromDeserializable;
+use crate::xml::enums::PlayerTeam;
+use crate::xml::recording::{
+    RecordedMove as XmlRecordedMove, RecordedTurn as XmlRecordedTurn, Recording as XmlRecording,
+};
+use crate::xml::serialization::{escape_attribute, from_str};
+
+use super::strategy::SearchInfo;
+
+#[derive(Debug, Clone)]
+pub struct RecordedMove {
+    pub sent_move: Move,
+    pub time_used: Duration,
+    pub search_info: Option<SearchInfo>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordedTurn {
+    pub game_state: GameState,
+    pub sent_move: Option<RecordedMove>,
+}
+
+/// Every game state received and every move sent during a single game.
+#[derive(Debug, Clone)]
+pub struct GameRecording {
+    pub room_id: String,
+    pub own_team: Option<PlayerTeam>,
+    pub turns: Vec<RecordedTurn>,
+}
+
+/// Replaces every character of the room id that isn't an ASCII letter, a
+/// digit, `_` or `-`, so the id can be used in a file name.
+pub fn room_file_stem(room_id: &str) -> String {
+    room_id
+        .chars()
+        .map(|character| match character {
+            'A'..='Z' | 'a'..='z' | '0'..='9' | '_' | '-' => character,
+            _ => '_',
+        })
+        .collect()
+}
+
+fn move_to_xml(r#move: &Move) -> String {
+    format!(
+        "<from x=\"{}\" y=\"{}\"/><to x=\"{}\" y=\"{}\"/>",
+        r#move.from.x, r#move.from.y, r#move.to.x, r#move.to.y
+    )
+}
+
+impl RecordedMove {
+    pub fn to_xml(&self) -> String {
+        let search_info_declaration = match &self.search_info {
+            Some(search_info) => {
+                let pv_declaration = search_info
+                    .principal_variation
+                    .iter()
+                    .map(|pv_move| format!("<entry>{}</entry>", move_to_xml(pv_move)))
+                    .collect::<String>();
+                format!(
+                    " depth=\"{}\" score=\"{}\">{}<pv>{}</pv>",
+                    search_info.depth,
+                    search_info.score,
+                    move_to_xml(&self.sent_move),
+                    pv_declaration
+                )
+            }
+            None => format!(">{}", move_to_xml(&self.sent_move)),
+        };
+
+        format!(
+            "<move timeUs=\"{}\"{}</move>",
+            self.time_used.as_micros(),
+            search_info_declaration
+        )
+    }
+}
+
+impl GameRecording {
+    pub fn new(room_id: String, own_team: Option<PlayerTeam>) -> Self {
+        Self {
+            room_id,
+            own_team,
+            turns: Vec::new(),
+        }
+    }
+
+    pub fn record_game_state(&mut self, game_state: GameState) {
+        self.turns.push(RecordedTurn {
+            game_state,
+            sent_move: None,
+        });
+    }
+
+    /// Attaches the sent move to the last recorded game state.
+    pub fn record_move(&mut self, recorded_move: RecordedMove) {
+        match self.turns.last_mut() {
+            Some(turn) => turn.sent_move = Some(recorded_move),
+            None => log::warn!("Tried to record a move before any game state was recorded"),
+        }
+    }
+
+    pub fn to_xml(&self) -> String {
+        let team_declaration = match &self.own_team {
+            Some(PlayerTeam::One) => " team=\"ONE\"",
+            Some(PlayerTeam::Two) => " team=\"TWO\"",
+            None => "",
+        };
+
+        let turns_declaration = self
+            .turns
+            .iter()
+            .map(|turn| {
+                let move_declaration = turn
+                    .sent_move
+                    .as_ref()
+                    .map(|sent_move| sent_move.to_xml())
+                    .unwrap_or_default();
+                format!(
+                    "  <turn>\n    {}\n    {}\n  </turn>\n",
+                    turn.game_state.to_xml(),
+                    move_declaration
+                )
+            })
+            .collect::<String>();
+
+        format!(
+            "<recording roomId=\"{}\"{}>\n{}</recording>\n",
+            escape_attribute(&self.room_id),
+            team_declaration,
+            turns_declaration
+        )
+    }
+
+    pub fn file_path(&self, directory: &Path) -> PathBuf {
+        directory.join(format!("{}.xml", room_file_stem(&self.room_id)))
+    }
+
+    /// Writes the recording to `<directory>/<room id>.xml`, replacing any
+    /// previous version of the file.
+    pub fn save(&self, directory: &Path) -> Result<PathBuf, Error> {
+        let path = self.file_path(directory);
+        let write_result =
+            fs::create_dir_all(directory).and_then(|_| fs::write(&path, self.to_xml()));
+
+        match write_result {
+            Ok(_) => Ok(path),
+            Err(error) => Err(Error::IOError(error)),
+        }
+    }
+}
+
+impl FromDeserializable<'_, XmlRecordedMove> for RecordedMove {
+    fn from_deserializable(deserializable: &XmlRecordedMove) -> Result<Self, Error> {
+        let sent_move = Move {
+            from: Coordinates::from(&deserializable.from),
+            to: Coordinates::from(&deserializable.to),
+        };
+
+        let search_info = match (deserializable.depth, deserializable.score) {
+            (Some(depth), Some(score)) => {
+                let principal_variation = deserializable
+                    .pv
+                    .as_ref()
+                    .map(|pv| {
+                        pv.entries
+                            .iter()
+                            .map(|entry| Move {
+                                from: Coordinates::from(&entry.from),
+                                to: Coordinates::from(&entry.to),
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Some(SearchInfo {
+                    depth,
+                    score,
+                    principal_variation,
+                    ..SearchInfo::default()
+                })
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            sent_move,
+            time_used: Duration::from_micros(deserializable.time_us),
+            search_info,
+        })
+    }
+}
+
+impl FromDeserializable<'_, XmlRecordedTurn> for RecordedTurn {
+    fn from_deserializable(deserializable: &XmlRecordedTurn) -> Result<Self, Error> {
+        let game_state = GameState::from_deserializable(&deserializable.state)?;
+        let sent_move = match &deserializable.sent_move {
+            Some(sent_move) => Some(RecordedMove::from_deserializable(sent_move)?),
+            None => None,
+        };
+
+        Ok(Self {
+            game_state,
+            sent_move,
+        })
+    }
+}
+
+impl FromDeserializable<'_, XmlRecording> for GameRecording {
+    fn from_deserializable(deserializable: &XmlRecording) -> Result<Self, Error> {
+        let mut turns = Vec::with_capacity(deserializable.turns.len());
+        for turn in deserializable.turns.iter() {
+            turns.push(RecordedTurn::from_deserializable(turn)?);
+        }
+
+        Ok(Self {
+            room_id: deserializable.room_id.clone(),
+            own_team: deserializable.team.clone(),
+            turns,
+        })
+    }
+}
+
+pub fn load_recording<P: AsRef<Path>>(path: P) -> Result<GameRecording, Error> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) => return Err(Error::IOError(error)),
+    };
+
+    let recording = from_str::<XmlRecording>(&text)?;
+    GameRecording::from_deserializable(&recording)
+}
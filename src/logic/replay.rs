@@ -0,0 +1,71 @@
+use crate::game::{game_state::GameState, notation::format_move_in, render::BoardRenderer};
+
+use super::recording::GameRecording;
+use super::strategy::Strategy;
+
+/// Steps through the given game states, printing each board together with the
+/// move that led to the next state, and reports every move that differs from
+/// the one the strategy would have played. If the game states come from a
+/// game recording, the recorded search information is printed as well.
+pub fn step_through_replay(
+    game_states: &[GameState],
+    recording: Option<&GameRecording>,
+    strategy: &mut dyn Strategy,
+    renderer: &BoardRenderer,
+) {
+    let mut differing_moves = 0;
+
+    for window in game_states.windows(2) {
+        let game_state = &window[0];
+        let next_game_state = &window[1];
+
+        println!("{}", renderer.render(game_state));
+
+        let played_move = match &next_game_state.last_move {
+            Some(played_move) => played_move,
+            None => {
+                println!("No move recorded for this turn\n");
+                continue;
+            }
+        };
+        println!("Played move: {}", format_move_in(game_state, played_move));
+
+        let recorded_move = recording.and_then(|recording| {
+            recording
+                .turns
+                .iter()
+                .find(|turn| turn.game_state.turn == game_state.turn)
+                .and_then(|turn| turn.sent_move.as_ref())
+        });
+        if let Some(recorded_move) = recorded_move {
+            println!("Recorded time used: {:?}", recorded_move.time_used);
+            if let Some(search_info) = &recorded_move.search_info {
+                println!(
+                    "Recorded search: depth {}, score {}, PV {:?}",
+                    search_info.depth, search_info.score, search_info.principal_variation
+                );
+            }
+        }
+
+        match strategy.calculate_move(game_state) {
+            Some(engine_move) if &engine_move != played_move => {
+                differing_moves += 1;
+                println!("Engine would have played: {:?}", engine_move);
+            }
+            Some(_) => println!("Engine agrees"),
+            None => println!("Engine found no move"),
+        }
+        println!();
+    }
+
+    if let Some(final_game_state) = game_states.last() {
+        println!("Final state | Result: {:?}", final_game_state.get_result());
+        println!("{}", renderer.render(final_game_state));
+    }
+
+    println!(
+        "Engine would have played differently in {} of {} turns",
+        differing_moves,
+        game_states.len().saturating_sub(1)
+    );
+}
@@ -0,0 +1,442 @@
+use std::time::{Duration, Instant};
+
+use crate::game::{
+    game_state::{GameState, GameStateResult, ROUND_LIMIT_TURN},
+    history::GameHistory,
+    moves::Move,
+    zobrist::position_hash,
+};
+
+use super::evaluation::evaluate;
+use super::proof::{ProofLimits, ProofResult, ProofSearch};
+use super::see::static_exchange_evaluation;
+use super::solver::{is_sharp, Solver, SolverResult};
+use super::strategy::{SearchInfo, Strategy};
+
+/// The score of a won position. Wins found after fewer plies score higher.
+pub const WIN_SCORE: i32 = 100_000;
+pub const MAX_DEPTH: u32 = 64;
+
+const INFINITY: i32 = WIN_SCORE + 1;
+
+/// How often (in nodes) the time limit is checked.
+const TIME_CHECK_INTERVAL: u64 = 1024;
+
+/// The plies and the share of the move time the strategy spends on looking
+/// for a forced win in sharp positions before searching.
+const SOLVER_PLIES: u32 = 5;
+const SOLVER_TIME_DIVISOR: u32 = 4;
+
+/// The plies before the round limit from which on the strategy tries to prove
+/// the result, and the share of the move time it spends on the proof.
+const PROOF_SEARCH_PLIES: u32 = 12;
+const PROOF_SEARCH_TIME_DIVISOR: u32 = 2;
+
+/// Limits for a search. Without limits, the search runs until the end of the
+/// game is reached in every line.
+#[derive(Debug, Clone, Default)]
+pub struct SearchLimits {
+    pub max_depth: Option<u32>,
+    pub move_time: Option<Duration>,
+}
+
+/// A move at the root of the search, its score from the point of view of the
+/// team to move and the principal variation starting with the move.
+#[derive(Debug, Clone)]
+pub struct RootMove {
+    pub root_move: Move,
+    pub score: i32,
+    pub principal_variation: Vec<Move>,
+}
+
+/// The result of a completed iteration of the search.
+#[derive(Debug, Clone)]
+pub struct SearchReport {
+    pub depth: u32,
+    pub nodes: u64,
+    pub elapsed: Duration,
+    /// The best root moves, best first. Only the first `multi_pv` moves carry
+    /// exact scores.
+    pub root_moves: Vec<RootMove>,
+}
+
+impl SearchReport {
+    pub fn best_move(&self) -> Option<&RootMove> {
+        self.root_moves.first()
+    }
+
+    pub fn search_info(&self) -> Option<SearchInfo> {
+        self.best_move().map(|best_move| SearchInfo {
+            depth: self.depth,
+            score: best_move.score,
+            principal_variation: best_move.principal_variation.clone(),
+            nodes: self.nodes,
+        })
+    }
+}
+
+/// Returns true if the score is a forced win or loss.
+pub fn is_decisive_score(score: i32) -> bool {
+    score.abs() >= WIN_SCORE - MAX_DEPTH as i32
+}
+
+/// Orders moves that score an amber first, then captures that don't lose
+/// material in the following exchange, the quiet moves and finally the
+/// losing captures.
+fn move_order_key(game_state: &GameState, r#move: &Move) -> i32 {
+    match game_state.annotate_move(r#move) {
+        Some(annotated_move) if annotated_move.scores_amber() => 0,
+        Some(annotated_move) if annotated_move.is_capture() => {
+            if static_exchange_evaluation(game_state, r#move) >= 0 {
+                1
+            } else {
+                3
+            }
+        }
+        _ => 2,
+    }
+}
+
+fn ordered_moves(game_state: &GameState) -> Vec<Move> {
+    let mut moves = game_state.legal_moves().collect::<Vec<Move>>();
+    moves.sort_by_key(|r#move| move_order_key(game_state, r#move));
+    moves
+}
+
+/// An iterative deepening alpha-beta search.
+pub struct Search {
+    limits: SearchLimits,
+    start_time: Instant,
+    nodes: u64,
+    aborted: bool,
+    can_abort: bool,
+    /// The position hashes of the game history before the root position.
+    history_hashes: Vec<u64>,
+    /// The position hashes of the history and the current search path.
+    path_hashes: Vec<u64>,
+}
+
+impl Search {
+    pub fn new(limits: SearchLimits) -> Self {
+        Self {
+            limits,
+            start_time: Instant::now(),
+            nodes: 0,
+            aborted: false,
+            can_abort: false,
+            history_hashes: Vec::new(),
+            path_hashes: Vec::new(),
+        }
+    }
+
+    /// Lets the search score positions that already occurred in the game as
+    /// draws. Repetitions don't end the game, but a line that returns to an
+    /// earlier position made no progress.
+    pub fn with_history(mut self, history: &GameHistory) -> Self {
+        self.history_hashes = history.position_hashes();
+        self
+    }
+
+    fn check_time(&mut self) {
+        if !self.can_abort || !self.nodes.is_multiple_of(TIME_CHECK_INTERVAL) {
+            return;
+        }
+        if let Some(move_time) = self.limits.move_time {
+            if self.start_time.elapsed() >= move_time {
+                self.aborted = true;
+            }
+        }
+    }
+
+    fn negamax(
+        &mut self,
+        game_state: &GameState,
+        depth: u32,
+        ply: u32,
+        mut alpha: i32,
+        beta: i32,
+        principal_variation: &mut Vec<Move>,
+    ) -> i32 {
+        self.nodes += 1;
+        self.check_time();
+        if self.aborted {
+            return 0;
+        }
+
+        // A finished game keeps its result even if the position repeats
+        match game_state.get_result() {
+            GameStateResult::Player(team) if team == game_state.get_current_team() => {
+                return WIN_SCORE - ply as i32
+            }
+            GameStateResult::Player(_) => return -(WIN_SCORE - ply as i32),
+            GameStateResult::Draw => return 0,
+            GameStateResult::Nothing => {}
+        }
+
+        let hash = position_hash(game_state);
+        if self.path_hashes.contains(&hash) {
+            return 0;
+        }
+        if depth == 0 || game_state.turn >= ROUND_LIMIT_TURN {
+            return evaluate(game_state);
+        }
+
+        let moves = ordered_moves(game_state);
+        if moves.is_empty() {
+            return evaluate(game_state);
+        }
+
+        self.path_hashes.push(hash);
+        let mut best_score = -INFINITY;
+        let mut child_variation = Vec::new();
+        for r#move in moves.iter() {
+            let mut child = game_state.clone();
+            if child.perform_move(r#move).is_err() {
+                continue;
+            }
+
+            child_variation.clear();
+            let score = -self.negamax(
+                &child,
+                depth - 1,
+                ply + 1,
+                -beta,
+                -alpha,
+                &mut child_variation,
+            );
+            if self.aborted {
+                self.path_hashes.pop();
+                return 0;
+            }
+
+            if score > best_score {
+                best_score = score;
+                if score > alpha {
+                    alpha = score;
+                    principal_variation.clear();
+                    principal_variation.push(r#move.clone());
+                    principal_variation.extend(child_variation.iter().cloned());
+                }
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+        self.path_hashes.pop();
+
+        best_score
+    }
+
+    /// Searches all root moves to the given depth. The best `multi_pv` moves
+    /// get exact scores, the others are only known to be worse.
+    fn search_root(
+        &mut self,
+        game_state: &GameState,
+        root_moves: &[Move],
+        depth: u32,
+        multi_pv: usize,
+    ) -> Option<Vec<RootMove>> {
+        let mut results: Vec<RootMove> = Vec::new();
+
+        for root_move in root_moves.iter() {
+            let mut child = game_state.clone();
+            if child.perform_move(root_move).is_err() {
+                continue;
+            }
+
+            // Only moves that could enter the best `multi_pv` moves need an
+            // exact score
+            let alpha = if results.len() >= multi_pv {
+                let mut scores = results.iter().map(|result| result.score).collect::<Vec<i32>>();
+                scores.sort_unstable_by(|a, b| b.cmp(a));
+                scores[multi_pv - 1]
+            } else {
+                -INFINITY
+            };
+
+            let mut child_variation = Vec::new();
+            let score = -self.negamax(&child, depth - 1, 1, -INFINITY, -alpha, &mut child_variation);
+            if self.aborted {
+                return None;
+            }
+
+            let mut principal_variation = vec![root_move.clone()];
+            principal_variation.extend(child_variation);
+            results.push(RootMove {
+                root_move: root_move.clone(),
+                score,
+                principal_variation,
+            });
+        }
+
+        // Stable, so that moves with equal scores keep the previous order
+        results.sort_by_key(|result| std::cmp::Reverse(result.score));
+        Some(results)
+    }
+
+    /// Runs the search until a limit is reached and calls `on_iteration` after
+    /// every completed depth. Returns the report of the last completed depth.
+    pub fn run(
+        &mut self,
+        game_state: &GameState,
+        multi_pv: usize,
+        on_iteration: &mut dyn FnMut(&SearchReport),
+    ) -> Option<SearchReport> {
+        self.start_time = Instant::now();
+        self.nodes = 0;
+        self.aborted = false;
+
+        // The history usually ends with the root position itself
+        let root_hash = position_hash(game_state);
+        self.path_hashes = self.history_hashes.clone();
+        if self.path_hashes.last() != Some(&root_hash) {
+            self.path_hashes.push(root_hash);
+        }
+
+        let multi_pv = multi_pv.max(1);
+        let mut root_moves = ordered_moves(game_state);
+        if root_moves.is_empty() || game_state.get_result() != GameStateResult::Nothing {
+            return None;
+        }
+
+        let remaining_plies = ROUND_LIMIT_TURN.saturating_sub(game_state.turn).max(1);
+        let max_depth = self
+            .limits
+            .max_depth
+            .unwrap_or(MAX_DEPTH)
+            .min(MAX_DEPTH)
+            .min(remaining_plies);
+
+        let mut last_report = None;
+        for depth in 1..=max_depth {
+            // The first iteration always completes, so that there is a move
+            self.can_abort = depth > 1;
+
+            let results = match self.search_root(game_state, &root_moves, depth, multi_pv) {
+                Some(results) => results,
+                None => break,
+            };
+            root_moves = results.iter().map(|result| result.root_move.clone()).collect();
+
+            let report = SearchReport {
+                depth,
+                nodes: self.nodes,
+                elapsed: self.start_time.elapsed(),
+                root_moves: results,
+            };
+            on_iteration(&report);
+
+            let decided = report
+                .best_move()
+                .is_none_or(|best_move| is_decisive_score(best_move.score));
+            last_report = Some(report);
+            if decided {
+                break;
+            }
+            if let Some(move_time) = self.limits.move_time {
+                if self.start_time.elapsed() >= move_time {
+                    break;
+                }
+            }
+        }
+
+        last_report
+    }
+}
+
+/// A strategy that plays the best move of an iterative deepening alpha-beta
+/// search within the move time of the engine configuration.
+pub struct AlphaBetaStrategy {
+    pub move_time: Duration,
+    last_search_info: Option<SearchInfo>,
+    proof_search: ProofSearch,
+}
+
+impl AlphaBetaStrategy {
+    pub fn new(move_time: Duration) -> Self {
+        Self {
+            move_time,
+            last_search_info: None,
+            proof_search: ProofSearch::new(ProofLimits::default()),
+        }
+    }
+
+    /// Sets the memory the proof-number search may use for its table.
+    pub fn with_proof_memory(mut self, memory_mb: usize) -> Self {
+        self.proof_search = ProofSearch::new(ProofLimits {
+            memory_mb,
+            ..ProofLimits::default()
+        });
+        self
+    }
+
+    /// Plays the proven move once the result of the position is known. Lost
+    /// positions are left to the search, which makes the loss hardest to find.
+    fn proven_move(&mut self, game_state: &GameState) -> Option<Move> {
+        self.proof_search
+            .set_move_time(Some(self.move_time / PROOF_SEARCH_TIME_DIVISOR));
+        let (r#move, score) = match self.proof_search.solve(game_state) {
+            ProofResult::Win(r#move) => (r#move, WIN_SCORE),
+            ProofResult::Draw(r#move) => (r#move, 0),
+            ProofResult::Loss | ProofResult::Unknown => return None,
+        };
+
+        self.last_search_info = Some(SearchInfo {
+            depth: ROUND_LIMIT_TURN.saturating_sub(game_state.turn),
+            score,
+            principal_variation: vec![r#move.clone()],
+            nodes: self.proof_search.nodes(),
+        });
+        Some(r#move)
+    }
+}
+
+impl Strategy for AlphaBetaStrategy {
+    fn calculate_move(&mut self, game_state: &GameState) -> Option<Move> {
+        self.calculate_move_with_history(game_state, &GameHistory::new())
+    }
+
+    fn calculate_move_with_history(
+        &mut self,
+        game_state: &GameState,
+        history: &GameHistory,
+    ) -> Option<Move> {
+        let start_time = Instant::now();
+        if ROUND_LIMIT_TURN.saturating_sub(game_state.turn) <= PROOF_SEARCH_PLIES {
+            if let Some(r#move) = self.proven_move(game_state) {
+                return Some(r#move);
+            }
+        }
+        if is_sharp(game_state) {
+            let mut solver =
+                Solver::new(SOLVER_PLIES).with_move_time(self.move_time / SOLVER_TIME_DIVISOR);
+            if let SolverResult::Won(line) = solver.solve(game_state) {
+                self.last_search_info = Some(SearchInfo {
+                    depth: line.len() as u32,
+                    score: WIN_SCORE - line.len() as i32,
+                    principal_variation: line.clone(),
+                    nodes: solver.nodes(),
+                });
+                return line.into_iter().next();
+            }
+        }
+
+        let mut search = Search::new(SearchLimits {
+            max_depth: None,
+            move_time: Some(self.move_time.saturating_sub(start_time.elapsed())),
+        })
+        .with_history(history);
+        let report = search.run(game_state, 1, &mut |_| {});
+
+        self.last_search_info = report.as_ref().and_then(|report| report.search_info());
+        report
+            .and_then(|report| report.best_move().map(|best_move| best_move.root_move.clone()))
+            .or_else(|| {
+                game_state.legal_moves().next()
+            })
+    }
+
+    fn search_info(&self) -> Option<SearchInfo> {
+        self.last_search_info.clone()
+    }
+}
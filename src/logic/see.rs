@@ -0,0 +1,71 @@
+//! Static exchange evaluation: the outcome of a capture once both teams have
+//! captured on the target square for as long as it pays off.
+//!
+//! Exchanges are short in this game. A capture either scores an amber and
+//! empties the square, or leaves a stack, and recapturing a stack always
+//! scores an amber.
+
+use crate::game::{game_state::GameState, moves::Move};
+use crate::util::coordinates::Coordinates;
+use crate::xml::enums::PlayerTeam;
+
+use super::evaluation::{AMBER_VALUE, PIECE_VALUE, STACKED_BONUS};
+
+/// The ambers and pieces of the team minus those of the opponent.
+fn material_balance(game_state: &GameState, team: &PlayerTeam) -> i32 {
+    let (own_ambers, opponent_ambers) = match team {
+        PlayerTeam::One => (game_state.ambers.0, game_state.ambers.1),
+        PlayerTeam::Two => (game_state.ambers.1, game_state.ambers.0),
+    };
+    let mut balance = AMBER_VALUE * (own_ambers - opponent_ambers);
+
+    for piece in game_state.board.pieces.values() {
+        let mut piece_value = PIECE_VALUE;
+        if piece.is_stacked() {
+            piece_value += STACKED_BONUS;
+        }
+        if &piece.team == team {
+            balance += piece_value;
+        } else {
+            balance -= piece_value;
+        }
+    }
+
+    balance
+}
+
+/// Performs the move and returns the resulting game state and the change of
+/// the material balance for the team that moved.
+fn play(game_state: &GameState, r#move: &Move) -> Option<(GameState, i32)> {
+    let team = game_state.get_current_team();
+    let mut next_game_state = game_state.clone();
+    next_game_state.perform_move(r#move).ok()?;
+    let gain = material_balance(&next_game_state, &team) - material_balance(game_state, &team);
+    Some((next_game_state, gain))
+}
+
+/// The best gain for the team to move from capturing on the square, or 0 if
+/// it is better not to capture at all.
+fn exchange_on(game_state: &GameState, square: &Coordinates) -> i32 {
+    if game_state.get_end_reason().is_some() {
+        return 0;
+    }
+
+    game_state
+        .legal_captures()
+        .filter(|capture| &capture.to == square)
+        .filter_map(|capture| play(game_state, &capture))
+        .map(|(next_game_state, gain)| gain - exchange_on(&next_game_state, square))
+        .fold(0, i32::max)
+}
+
+/// Returns the change of ambers and material for the team playing the move
+/// after the best sequence of recaptures on its target square. Works for quiet
+/// moves as well, which lose material if the target square is attacked.
+/// Illegal moves evaluate to 0.
+pub fn static_exchange_evaluation(game_state: &GameState, r#move: &Move) -> i32 {
+    match play(game_state, r#move) {
+        Some((next_game_state, gain)) => gain - exchange_on(&next_game_state, &r#move.to),
+        None => 0,
+    }
+}
@@ -0,0 +1,66 @@
+use rand::{seq::SliceRandom, Rng};
+
+use crate::game::{
+    board::Board,
+    game_state::{GameState, GameStateResult, ROUND_LIMIT_TURN},
+    moves::Move,
+};
+use crate::util::error::Error;
+use crate::xml::enums::PlayerTeam;
+
+use super::strategy::Strategy;
+
+/// The maximum number of turns before a self-play game is aborted, as a
+/// safeguard against games that are never decided.
+pub const MAX_TURNS: u32 = ROUND_LIMIT_TURN;
+
+/// Plays a game between two strategies, starting from the given game state,
+/// and returns the final game state.
+pub fn play_game(
+    mut game_state: GameState,
+    strategy_one: &mut dyn Strategy,
+    strategy_two: &mut dyn Strategy,
+) -> GameState {
+    while game_state.get_result() == GameStateResult::Nothing && game_state.turn < MAX_TURNS {
+        let calculated_move = match game_state.get_current_team() {
+            PlayerTeam::One => strategy_one.calculate_move(&game_state),
+            PlayerTeam::Two => strategy_two.calculate_move(&game_state),
+        };
+
+        let calculated_move = match calculated_move {
+            Some(calculated_move) => calculated_move,
+            None => {
+                log::warn!("No move found in turn {}", game_state.turn);
+                break;
+            }
+        };
+
+        if let Err(error) = game_state.perform_move(&calculated_move) {
+            log::error!("Error while trying to perform move on game state: {:?}", error);
+            break;
+        }
+    }
+
+    game_state
+}
+
+/// Plays random moves from a random start position and returns the game
+/// states before every move. The moves are sorted before one is picked, so a
+/// seeded `rng` always plays the same game.
+pub fn random_game_positions<R: Rng>(rng: &mut R) -> Result<Vec<GameState>, Error> {
+    let mut positions = Vec::new();
+    let mut game_state = GameState::new(Board::random_start_position_with(rng));
+
+    while game_state.get_result() == GameStateResult::Nothing && game_state.turn < MAX_TURNS {
+        positions.push(game_state.clone());
+        let mut possible_moves = game_state.legal_moves().collect::<Vec<Move>>();
+        possible_moves.sort_by_key(|r#move| (r#move.from.x, r#move.from.y, r#move.to.x, r#move.to.y));
+        let next_move = match possible_moves.choose(rng) {
+            Some(next_move) => next_move.clone(),
+            None => break,
+        };
+        game_state.perform_move(&next_move)?;
+    }
+
+    Ok(positions)
+}
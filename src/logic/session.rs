@@ -0,0 +1,112 @@
+use crate::game::{
+    game_state::{GameState, GameStateResult},
+    history::GameHistory,
+    moves::Move,
+};
+use crate::util::error::Error;
+use crate::xml::enums::PlayerTeam;
+
+use super::selfplay::MAX_TURNS;
+use super::strategy::{SearchInfo, Strategy};
+
+/// The move of the engine together with the search information of the
+/// strategy and the game state it was calculated for.
+#[derive(Debug, Clone)]
+pub struct EngineMove {
+    pub engine_move: Move,
+    pub search_info: Option<SearchInfo>,
+    pub game_state: GameState,
+}
+
+/// A game between a human and a strategy without a server, keeping the
+/// previous game states for undoing moves.
+pub struct PlaySession {
+    pub game_state: GameState,
+    pub human_team: PlayerTeam,
+    pub history: Vec<GameState>,
+    pub last_engine_move: Option<EngineMove>,
+}
+
+impl PlaySession {
+    pub fn new(game_state: GameState, human_team: PlayerTeam) -> Self {
+        Self {
+            game_state,
+            human_team,
+            history: Vec::new(),
+            last_engine_move: None,
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.game_state.get_result() != GameStateResult::Nothing || self.game_state.turn >= MAX_TURNS
+    }
+
+    pub fn is_human_to_move(&self) -> bool {
+        !self.is_over() && self.game_state.get_current_team() == self.human_team
+    }
+
+    pub fn legal_moves(&self) -> Vec<Move> {
+        self.game_state.legal_moves().collect()
+    }
+
+    fn perform_move(&mut self, r#move: &Move) -> Result<(), Error> {
+        let mut next_game_state = self.game_state.clone();
+        next_game_state.perform_move(r#move)?;
+        self.history
+            .push(std::mem::replace(&mut self.game_state, next_game_state));
+        Ok(())
+    }
+
+    /// Performs the move of the human after checking that it is legal.
+    pub fn play_human_move(&mut self, r#move: &Move) -> Result<(), Error> {
+        if !self.is_human_to_move() {
+            return Err(Error::SimpleError(String::from("It's not your turn")));
+        }
+        if !self.legal_moves().contains(r#move) {
+            return Err(Error::SimpleError(String::from("Illegal move")));
+        }
+        self.perform_move(r#move)
+    }
+
+    /// Lets the strategy calculate and perform a move if it is its turn.
+    pub fn play_engine_move(&mut self, strategy: &mut dyn Strategy) -> Result<Option<&EngineMove>, Error> {
+        if self.is_over() || self.is_human_to_move() {
+            return Ok(None);
+        }
+
+        let mut game_states = self.history.clone();
+        game_states.push(self.game_state.clone());
+        let history = GameHistory::from_game_states(&game_states);
+
+        let engine_move = match strategy.calculate_move_with_history(&self.game_state, &history) {
+            Some(engine_move) => engine_move,
+            None => return Err(Error::SimpleError(String::from("The engine found no move"))),
+        };
+        let game_state = self.game_state.clone();
+        self.perform_move(&engine_move)?;
+        self.last_engine_move = Some(EngineMove {
+            engine_move,
+            search_info: strategy.search_info(),
+            game_state,
+        });
+        Ok(self.last_engine_move.as_ref())
+    }
+
+    /// Takes back moves until it is the human's turn again, i.e. the last
+    /// move of the engine and the human's move before it. Returns false if
+    /// there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let mut undone = false;
+        while let Some(previous_game_state) = self.history.pop() {
+            self.game_state = previous_game_state;
+            undone = true;
+            if self.is_human_to_move() {
+                break;
+            }
+        }
+        if undone {
+            self.last_engine_move = None;
+        }
+        undone
+    }
+}
@@ -0,0 +1,214 @@
+//! A solver for forced wins within a number of plies, the equivalent of a
+//! mate search in chess. Wins are usually reached by scoring the second amber.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use crate::game::{
+    game_state::{GameState, GameStateResult},
+    moves::Move,
+    zobrist::position_hash,
+};
+use crate::xml::enums::PlayerTeam;
+
+/// How often (in nodes) the time limit is checked.
+const TIME_CHECK_INTERVAL: u64 = 1024;
+
+#[derive(Debug, Clone)]
+pub enum SolverResult {
+    /// The team to move wins with this line against every defence. The line
+    /// follows the defence that resists longest.
+    Won(Vec<Move>),
+    /// There is no forced win within the searched plies.
+    NotFound,
+    /// The time or node limit was reached before a win was found.
+    Aborted,
+}
+
+/// Searches for a forced win of the team to move within `max_plies` plies.
+pub struct Solver {
+    max_plies: u32,
+    move_time: Option<Duration>,
+    max_nodes: Option<u64>,
+    start_time: Instant,
+    nodes: u64,
+    aborted: bool,
+    /// Positions, turns and remaining plies known not to be won. The turn is
+    /// part of the key because the position hash ignores it, but the round
+    /// end and the round limit depend on it.
+    failed: HashSet<(u64, u32, u32)>,
+}
+
+impl Solver {
+    pub fn new(max_plies: u32) -> Self {
+        Self {
+            max_plies,
+            move_time: None,
+            max_nodes: None,
+            start_time: Instant::now(),
+            nodes: 0,
+            aborted: false,
+            failed: HashSet::new(),
+        }
+    }
+
+    pub fn with_move_time(mut self, move_time: Duration) -> Self {
+        self.move_time = Some(move_time);
+        self
+    }
+
+    pub fn with_max_nodes(mut self, max_nodes: u64) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    fn count_node(&mut self) {
+        self.nodes += 1;
+        if self
+            .max_nodes
+            .is_some_and(|max_nodes| self.nodes >= max_nodes)
+        {
+            self.aborted = true;
+        }
+        if self.nodes.is_multiple_of(TIME_CHECK_INTERVAL)
+            && self
+                .move_time
+                .is_some_and(|move_time| self.start_time.elapsed() >= move_time)
+        {
+            self.aborted = true;
+        }
+    }
+
+    /// Ambers first, then captures, as they are the moves that can win.
+    fn ordered_moves(game_state: &GameState) -> Vec<Move> {
+        let mut moves = game_state.legal_moves().collect::<Vec<Move>>();
+        moves.sort_by_key(|r#move| match game_state.annotate_move(r#move) {
+            Some(annotated_move) if annotated_move.scores_amber() => 0,
+            Some(annotated_move) if annotated_move.is_capture() => 1,
+            _ => 2,
+        });
+        moves
+    }
+
+    /// Returns a winning line for the attacker, who is to move.
+    fn attacker_wins(
+        &mut self,
+        game_state: &GameState,
+        attacker: &PlayerTeam,
+        plies: u32,
+    ) -> Option<Vec<Move>> {
+        let key = (position_hash(game_state), game_state.turn, plies);
+        if self.failed.contains(&key) {
+            return None;
+        }
+        self.count_node();
+
+        for r#move in Self::ordered_moves(game_state) {
+            if self.aborted {
+                return None;
+            }
+            let mut child = game_state.clone();
+            if child.perform_move(&r#move).is_err() {
+                continue;
+            }
+
+            let line = match child.get_result() {
+                GameStateResult::Player(winner) if &winner == attacker => Some(Vec::new()),
+                GameStateResult::Nothing if plies > 1 => {
+                    self.defender_loses(&child, attacker, plies - 1)
+                }
+                _ => None,
+            };
+            if let Some(line) = line {
+                let mut winning_line = vec![r#move];
+                winning_line.extend(line);
+                return Some(winning_line);
+            }
+        }
+
+        if !self.aborted {
+            self.failed.insert(key);
+        }
+        None
+    }
+
+    /// Returns the line of the longest defence if every move of the
+    /// defender, who is to move, loses.
+    fn defender_loses(
+        &mut self,
+        game_state: &GameState,
+        attacker: &PlayerTeam,
+        plies: u32,
+    ) -> Option<Vec<Move>> {
+        self.count_node();
+
+        let mut longest_line: Option<Vec<Move>> = None;
+        for r#move in Self::ordered_moves(game_state) {
+            if self.aborted {
+                return None;
+            }
+            let mut child = game_state.clone();
+            if child.perform_move(&r#move).is_err() {
+                continue;
+            }
+
+            let line = match child.get_result() {
+                GameStateResult::Player(winner) if &winner == attacker => Vec::new(),
+                GameStateResult::Nothing if plies > 1 => {
+                    self.attacker_wins(&child, attacker, plies - 1)?
+                }
+                _ => return None,
+            };
+            if longest_line
+                .as_ref()
+                .is_none_or(|longest_line| line.len() + 1 > longest_line.len())
+            {
+                let mut defence = vec![r#move];
+                defence.extend(line);
+                longest_line = Some(defence);
+            }
+        }
+
+        // Without a legal move the game would already be over
+        longest_line
+    }
+
+    /// Searches with increasing plies, so that the shortest win is found.
+    pub fn solve(&mut self, game_state: &GameState) -> SolverResult {
+        self.start_time = Instant::now();
+        self.nodes = 0;
+        self.aborted = false;
+        self.failed.clear();
+
+        if game_state.get_result() != GameStateResult::Nothing {
+            return SolverResult::NotFound;
+        }
+        let attacker = game_state.get_current_team();
+
+        for plies in 1..=self.max_plies {
+            if let Some(line) = self.attacker_wins(game_state, &attacker, plies) {
+                return SolverResult::Won(line);
+            }
+            if self.aborted {
+                return SolverResult::Aborted;
+            }
+        }
+        SolverResult::NotFound
+    }
+}
+
+/// Whether a position is sharp enough to look for a forced win: a team is
+/// one amber away from winning, or the team to move can score an amber.
+pub fn is_sharp(game_state: &GameState) -> bool {
+    game_state.ambers.0 >= 1
+        || game_state.ambers.1 >= 1
+        || game_state.legal_moves().any(|r#move| {
+            game_state
+                .annotate_move(&r#move)
+                .is_some_and(|annotated_move| annotated_move.scores_amber())
+        })
+}
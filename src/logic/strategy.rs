@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use rand::{seq::SliceRandom, thread_rng};
+
+use crate::game::{game_state::GameState, history::GameHistory, moves::Move};
+use crate::util::config::EngineConfig;
+use crate::util::error::Error;
+
+use super::search::AlphaBetaStrategy;
+
+pub const STRATEGY_NAMES: [&str; 2] = ["random", "alphabeta"];
+
+/// Information about the search a strategy performed to find its last move.
+/// The alpha-beta search has no transposition table, so there are no table
+/// statistics to report.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchInfo {
+    pub depth: u32,
+    pub score: i32,
+    pub principal_variation: Vec<Move>,
+
+    pub nodes: u64,
+}
+
+pub trait Strategy {
+    fn calculate_move(&mut self, game_state: &GameState) -> Option<Move>;
+
+    /// Calculates a move knowing the previous game states and moves of the
+    /// game. Strategies that don't use the history calculate the move from
+    /// the game state alone.
+    fn calculate_move_with_history(
+        &mut self,
+        game_state: &GameState,
+        _history: &GameHistory,
+    ) -> Option<Move> {
+        self.calculate_move(game_state)
+    }
+
+    /// Returns information about the search behind the last calculated move,
+    /// if the strategy performs a search at all.
+    fn search_info(&self) -> Option<SearchInfo> {
+        None
+    }
+}
+
+/// Picks a random move out of all possible moves for the current team.
+pub struct RandomStrategy;
+
+impl Strategy for RandomStrategy {
+    fn calculate_move(&mut self, game_state: &GameState) -> Option<Move> {
+        let possible_moves = game_state.legal_moves().collect::<Vec<Move>>();
+        let mut rng = thread_rng();
+        possible_moves.choose(&mut rng).cloned()
+    }
+}
+
+/// Creates the strategy with the given name. Strategies take the settings they
+/// need from the engine configuration and ignore the rest.
+pub fn strategy_from_name(
+    name: &str,
+    engine_config: &EngineConfig,
+) -> Result<Box<dyn Strategy>, Error> {
+    match name {
+        "random" => Ok(Box::new(RandomStrategy)),
+        "alphabeta" => Ok(Box::new(
+            AlphaBetaStrategy::new(Duration::from_millis(engine_config.move_time_ms()))
+                .with_proof_memory(engine_config.hash_size_mb),
+        )),
+        _ => Err(Error::SimpleError(format!(
+            "Unknown strategy '{}', expected one of: {}",
+            name,
+            STRATEGY_NAMES.join(", ")
+        ))),
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::{game_state::GameState, moves::Move, notation::format_move};
+use crate::util::error::Error;
+use crate::xml::enums::PlayerTeam;
+
+use super::strategy::SearchInfo;
+
+/// A machine-readable record of a single move calculation. The search
+/// related fields are only available when the strategy reports search
+/// information.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MoveTelemetry {
+    pub room_id: Option<String>,
+    pub turn: u32,
+    pub team: PlayerTeam,
+    pub ambers: (i32, i32),
+    pub legal_moves: usize,
+    pub chosen_move: String,
+    pub time_us: u64,
+
+    pub depth: Option<u32>,
+    pub nodes: Option<u64>,
+    pub nps: Option<u64>,
+    pub score: Option<i32>,
+    pub principal_variation: Vec<String>,
+}
+
+impl MoveTelemetry {
+    pub fn new(
+        room_id: Option<String>,
+        game_state: &GameState,
+        legal_moves: usize,
+        chosen_move: &Move,
+        time_used: Duration,
+        search_info: Option<&SearchInfo>,
+    ) -> Self {
+        let time_us = time_used.as_micros() as u64;
+        let nps = search_info.and_then(|info| (info.nodes * 1_000_000).checked_div(time_us));
+
+        Self {
+            room_id,
+            turn: game_state.turn,
+            team: game_state.get_current_team(),
+            ambers: game_state.ambers,
+            legal_moves,
+            chosen_move: format_move(chosen_move),
+            time_us,
+            depth: search_info.map(|info| info.depth),
+            nodes: search_info.map(|info| info.nodes),
+            nps,
+            score: search_info.map(|info| info.score),
+            principal_variation: search_info
+                .map(|info| info.principal_variation.iter().map(format_move).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        match serde_json::to_string(self) {
+            Ok(json) => Ok(json),
+            Err(error) => Err(Error::JsonError(error)),
+        }
+    }
+}
+
+/// Appends telemetry records to a JSON-lines file, one record per line.
+pub struct TelemetryWriter {
+    file: File,
+}
+
+impl TelemetryWriter {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let file = OpenOptions::new().create(true).append(true).open(path);
+        match file {
+            Ok(file) => Ok(Self { file }),
+            Err(error) => Err(Error::IOError(error)),
+        }
+    }
+
+    pub fn write(&mut self, telemetry: &MoveTelemetry) -> Result<(), Error> {
+        let line = telemetry.to_json()?;
+        match writeln!(self.file, "{}", line) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(Error::IOError(error)),
+        }
+    }
+}
+
+pub fn parse_telemetry(text: &str) -> Result<Vec<MoveTelemetry>, Error> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| match serde_json::from_str::<MoveTelemetry>(line) {
+            Ok(telemetry) => Ok(telemetry),
+            Err(error) => Err(Error::JsonError(error)),
+        })
+        .collect()
+}
+
+pub fn load_telemetry<P: AsRef<Path>>(path: P) -> Result<Vec<MoveTelemetry>, Error> {
+    match fs::read_to_string(path) {
+        Ok(text) => parse_telemetry(&text),
+        Err(error) => Err(Error::IOError(error)),
+    }
+}
+
+fn average<I: Iterator<Item = f64>>(values: I) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+    if count == 0 {
+        None
+    } else {
+        Some(sum / count as f64)
+    }
+}
+
+/// Aggregated statistics over the telemetry records of one or more games.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TelemetrySummary {
+    pub games: usize,
+    pub moves: usize,
+    pub average_time_us: Option<f64>,
+    pub max_time_us: Option<u64>,
+    pub average_legal_moves: Option<f64>,
+    pub average_depth: Option<f64>,
+    pub max_depth: Option<u32>,
+    pub average_nps: Option<f64>,
+}
+
+impl TelemetrySummary {
+    pub fn from_records(records: &[MoveTelemetry]) -> Self {
+        // Records without a room id are counted as a single game
+        let games = records
+            .iter()
+            .map(|record| record.room_id.as_deref())
+            .collect::<HashSet<_>>()
+            .len();
+
+        Self {
+            games,
+            moves: records.len(),
+            average_time_us: average(records.iter().map(|record| record.time_us as f64)),
+            max_time_us: records.iter().map(|record| record.time_us).max(),
+            average_legal_moves: average(records.iter().map(|record| record.legal_moves as f64)),
+            average_depth: average(records.iter().filter_map(|record| record.depth).map(f64::from)),
+            max_depth: records.iter().filter_map(|record| record.depth).max(),
+            average_nps: average(records.iter().filter_map(|record| record.nps).map(|nps| nps as f64)),
+        }
+    }
+}
+
+fn format_optional<T: Display>(value: Option<T>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => String::from("-"),
+    }
+}
+
+impl Display for TelemetrySummary {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(fmt, "Games:               {}", self.games)?;
+        writeln!(fmt, "Moves:               {}", self.moves)?;
+        writeln!(
+            fmt,
+            "Average time:        {}",
+            format_optional(self.average_time_us.map(|time| format!("{:.1} ms", time / 1000.0)))
+        )?;
+        writeln!(
+            fmt,
+            "Maximum time:        {}",
+            format_optional(self.max_time_us.map(|time| format!("{:.1} ms", time as f64 / 1000.0)))
+        )?;
+        writeln!(
+            fmt,
+            "Average legal moves: {}",
+            format_optional(self.average_legal_moves.map(|moves| format!("{:.1}", moves)))
+        )?;
+        writeln!(
+            fmt,
+            "Average depth:       {}",
+            format_optional(self.average_depth.map(|depth| format!("{:.2}", depth)))
+        )?;
+        writeln!(fmt, "Maximum depth:       {}", format_optional(self.max_depth))?;
+        write!(
+            fmt,
+            "Average nodes/s:     {}",
+            format_optional(self.average_nps.map(|nps| format!("{:.0}", nps)))
+        )
+    }
+}
@@ -0,0 +1,179 @@
+//! A static HTML page for stepping through a game in the browser. The page
+//! contains every board as inline SVG and needs no external assets.
+
+use crate::game::{
+    diagram::{render_svg, DiagramOptions},
+    game_state::{GameState, GameStateResult},
+    moves::Move,
+    notation::{format_move_in, format_variation},
+};
+
+use super::recording::GameRecording;
+use super::strategy::{SearchInfo, Strategy};
+
+/// A single turn of the viewer: the game state, the move played from it and
+/// the evaluation of the engine, if available.
+#[derive(Debug, Clone)]
+pub struct ViewerTurn {
+    pub game_state: GameState,
+    pub played_move: Option<Move>,
+    pub evaluation: Option<SearchInfo>,
+}
+
+/// Collects the turns of a game. Evaluations are taken from the recording if
+/// it contains search information for a turn, otherwise the strategy (if
+/// given) is asked for one.
+pub fn collect_viewer_turns(
+    game_states: &[GameState],
+    recording: Option<&GameRecording>,
+    mut strategy: Option<&mut dyn Strategy>,
+) -> Vec<ViewerTurn> {
+    game_states
+        .iter()
+        .enumerate()
+        .map(|(index, game_state)| {
+            let played_move = game_states
+                .get(index + 1)
+                .and_then(|next_game_state| next_game_state.last_move.clone());
+
+            let recorded_evaluation = recording.and_then(|recording| {
+                recording
+                    .turns
+                    .iter()
+                    .find(|turn| turn.game_state.turn == game_state.turn)
+                    .and_then(|turn| turn.sent_move.as_ref())
+                    .and_then(|sent_move| sent_move.search_info.clone())
+            });
+            let evaluation = match (recorded_evaluation, strategy.as_mut()) {
+                (Some(evaluation), _) => Some(evaluation),
+                (None, Some(strategy)) if game_state.get_result() == GameStateResult::Nothing => {
+                    strategy.calculate_move(game_state);
+                    strategy.search_info()
+                }
+                (None, _) => None,
+            };
+
+            ViewerTurn {
+                game_state: game_state.clone(),
+                played_move,
+                evaluation,
+            }
+        })
+        .collect()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn format_evaluation(game_state: &GameState, evaluation: &Option<SearchInfo>) -> String {
+    match evaluation {
+        Some(search_info) => format!(
+            "Engine: depth {}, score {}, PV {}",
+            search_info.depth,
+            search_info.score,
+            format_variation(game_state, &search_info.principal_variation)
+        ),
+        None => String::from("Engine: no evaluation"),
+    }
+}
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; background: #f4f1ea; color: #222; }
+main { display: flex; gap: 2em; align-items: flex-start; }
+.turn { display: none; }
+.turn.active { display: block; }
+.controls { margin: 1em 0; }
+.controls button { font-size: 1.1em; padding: 0.3em 1em; }
+.info { margin: 0.3em 0; }
+ol { max-height: 36em; overflow-y: auto; padding-left: 3em; margin: 0; }
+li { cursor: pointer; padding: 0.1em 0.4em; }
+li.active { background: #f39c12; color: #fff; }
+";
+
+const SCRIPT: &str = "
+var turns = document.querySelectorAll('.turn');
+var entries = document.querySelectorAll('li');
+var current = 0;
+function show(index) {
+  if (index < 0 || index >= turns.length) { return; }
+  turns[current].classList.remove('active');
+  entries[current].classList.remove('active');
+  current = index;
+  turns[current].classList.add('active');
+  entries[current].classList.add('active');
+  document.getElementById('position').textContent = (current + 1) + ' / ' + turns.length;
+}
+document.getElementById('first').onclick = function () { show(0); };
+document.getElementById('previous').onclick = function () { show(current - 1); };
+document.getElementById('next').onclick = function () { show(current + 1); };
+document.getElementById('last').onclick = function () { show(turns.length - 1); };
+entries.forEach(function (entry, index) { entry.onclick = function () { show(index); }; });
+document.onkeydown = function (event) {
+  if (event.key === 'ArrowLeft') { show(current - 1); }
+  if (event.key === 'ArrowRight') { show(current + 1); }
+};
+show(0);
+";
+
+/// Renders the turns as a self-contained HTML page with the board of every
+/// turn, controls for stepping through them and the list of played moves.
+pub fn render_viewer_html(title: &str, turns: &[ViewerTurn]) -> String {
+    let diagram_options = DiagramOptions::default();
+
+    let mut boards = String::new();
+    let mut move_list = String::new();
+    for viewer_turn in turns.iter() {
+        let game_state = &viewer_turn.game_state;
+        let played_move = match &viewer_turn.played_move {
+            Some(played_move) => format_move_in(game_state, played_move),
+            None => String::from("-"),
+        };
+        let result = match game_state.get_result() {
+            GameStateResult::Nothing => String::new(),
+            result => format!("<p class=\"info\">Result: {:?}</p>", result),
+        };
+
+        boards.push_str(&format!(
+            "<div class=\"turn\">\n<p class=\"info\"><b>Turn {}</b> | {:?} to move | Ambers {} : {}</p>\n\
+             {}<p class=\"info\">Played: {}</p>\n<p class=\"info\">{}</p>\n{}</div>\n",
+            game_state.turn,
+            game_state.get_current_team(),
+            game_state.ambers.0,
+            game_state.ambers.1,
+            render_svg(game_state, &diagram_options),
+            played_move,
+            escape_html(&format_evaluation(game_state, &viewer_turn.evaluation)),
+            result
+        ));
+        move_list.push_str(&format!(
+            "<li value=\"{}\">{}{}</li>\n",
+            game_state.turn,
+            played_move,
+            viewer_turn
+                .evaluation
+                .as_ref()
+                .map(|search_info| format!(" ({})", search_info.score))
+                .unwrap_or_default()
+        ));
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n\
+         <body>\n<h1>{}</h1>\n<main>\n<section>\n<div class=\"controls\">\
+         <button id=\"first\">&laquo;</button> <button id=\"previous\">&lsaquo; Previous</button> \
+         <span id=\"position\"></span> \
+         <button id=\"next\">Next &rsaquo;</button> <button id=\"last\">&raquo;</button></div>\n\
+         {}</section>\n<section>\n<h2>Moves</h2>\n<ol>\n{}</ol>\n</section>\n</main>\n\
+         <script>{}</script>\n</body>\n</html>\n",
+        escape_html(title),
+        STYLE,
+        escape_html(title),
+        boards,
+        move_list,
+        SCRIPT
+    )
+}
@@ -1,44 +1,39 @@
 extern crate log;
 
 mod args;
-mod game;
-mod logic;
-mod networking;
-mod protocol;
-mod util;
-mod xml;
+mod commands;
 
-use protocol::manager::*;
-use util::{error::Error, logger_setup::setup_logger};
+use socha_2022_rust::{config::Config, Error, setup_logger};
 
-use crate::{args::client::ClientArgs, logic::logic::Logic};
+use crate::args::command::{Command, Invocation};
 
-fn game_loop(protocol_manager: &mut ProtocolManager) -> Result<(), Error> {
-    // Wait for a join response from the server
-    let room_id = protocol_manager.wait_for_joined_response()?;
-    log::info!("Joined game: {}", room_id);
-
-    // Main protocol loop
-    let mut logic = Logic::new();
-
-    loop {
-        let message = protocol_manager.get_next_message()?;
-        match logic.process_server_side_message(protocol_manager, message) {
-            logic::logic::ClientState::Running => {}
-            logic::logic::ClientState::ShouldTerminate => {
-                return Ok(());
-            }
+fn main() -> Result<(), Error> {
+    let invocation = Invocation::collect()?;
+    let mut config = Config::load_or_default(invocation.global_args.config_path.as_deref())?;
+    invocation.global_args.apply_to_config(&mut config);
+    invocation.command.apply_to_config(&mut config);
+
+    setup_logger(&config.logging)?;
+    log::info!("Effective config: {}", config);
+    config.engine.warn_about_unused_settings();
+
+    match invocation.command {
+        Command::Play(client_args) => {
+            commands::play::run(client_args, &invocation.global_args, &config)
+        }
+        Command::Perft(perft_args) => commands::perft::run(perft_args, &config),
+        Command::Bench(bench_args) => commands::bench::run(bench_args, &config),
+        Command::Analyze(analyze_args) => commands::analyze::run(analyze_args, &config),
+        Command::Solve(solve_args) => commands::solve::run(solve_args, &config),
+        Command::SelfPlay(selfplay_args) => commands::selfplay::run(selfplay_args, &config),
+        Command::Replay(replay_args) => commands::replay::run(replay_args, &config),
+        Command::Telemetry(telemetry_args) => commands::telemetry::run(telemetry_args),
+        Command::Diagram(diagram_args) => commands::diagram::run(diagram_args),
+        Command::Viewer(viewer_args) => commands::viewer::run(viewer_args, &config),
+        Command::Tui(tui_args) => commands::tui::run(tui_args, &config),
+        Command::Help(usage) => {
+            println!("{}", usage);
+            Ok(())
         }
     }
 }
-
-fn main() -> Result<(), Error> {
-    setup_logger()?;
-
-    let collected_args = ClientArgs::collect()?;
-    let mut protocol_manager = ProtocolManager::from_args(collected_args)?;
-    protocol_manager.join_game()?;
-    log::info!("Starting game loop...");
-    game_loop(&mut protocol_manager)?;
-    Ok(())
-}
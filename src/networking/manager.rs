@@ -1,5 +1,5 @@
 use std::io::{Read, Write};
-use std::net::TcpStream;
+use std::net::{Shutdown, TcpStream};
 
 use crate::util::error::Error;
 
@@ -23,7 +23,7 @@ impl NetworkManager {
 
     pub fn write_text(&mut self, text: &str) -> Result<usize, Error> {
         let text_bytes = text.as_bytes();
-        let write_result = self.stream.write(&text_bytes);
+        let write_result = self.stream.write(text_bytes);
 
         match write_result {
             Ok(size) => Ok(size),
@@ -31,6 +31,13 @@ impl NetworkManager {
         }
     }
 
+    pub fn shutdown(&mut self) -> Result<(), Error> {
+        match self.stream.shutdown(Shutdown::Both) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(Error::IOError(error)),
+        }
+    }
+
     fn read_raw_exact(&mut self, length: usize) -> Result<Vec<u8>, Error> {
         let mut read_buffer = vec![0; length];
         let read_result = self.stream.read(&mut read_buffer);
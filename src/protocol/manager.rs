@@ -1,36 +1,29 @@
 extern crate quick_xml;
 extern crate serde;
 
-use std::convert::TryFrom;
+use std::collections::VecDeque;
 
 use crate::networking::manager::NetworkManager;
 use crate::util::error::Error;
 use crate::xml::serialization::from_str;
-use crate::xml::server::data::Received;
-use crate::{args::client::ClientArgs, xml::server::data::Joined};
+use crate::xml::server::data::Joined;
 
-use super::message::{ClientSideMessage, ServerSideMessage};
+use super::message::{ClientSideMessage, PreparedGame, ServerSideMessage};
 
 pub struct ProtocolManager {
     network_manager: NetworkManager,
-    client_args: ClientArgs,
+    pending_messages: VecDeque<ServerSideMessage>,
 }
 
 impl ProtocolManager {
-    fn from_valid_args(client_args: ClientArgs) -> Result<Self, Error> {
-        let host = client_args.host.clone();
-        let port = client_args.port;
+    pub fn connect(host: String, port: i32) -> Result<Self, Error> {
         let network_manager = NetworkManager::connect(host, port)?;
         Ok(Self {
             network_manager,
-            client_args: client_args.clone(),
+            pending_messages: VecDeque::new(),
         })
     }
 
-    pub fn from_args(client_args: ClientArgs) -> Result<Self, Error> {
-        Self::from_valid_args(client_args)
-    }
-
     pub fn send_client_side_message(&mut self, message: ClientSideMessage) -> Result<usize, Error> {
         let text = message.to_xml()?;
 
@@ -40,19 +33,45 @@ impl ProtocolManager {
         }
     }
 
-    pub fn join_game(&mut self) -> Result<usize, Error> {
-        let args_reservation = &self.client_args.reservation;
-        match args_reservation {
+    pub fn join_game(&mut self, reservation: Option<String>) -> Result<usize, Error> {
+        match reservation {
             Some(reservation) => {
-                let res_clone = reservation.clone();
-                self.send_client_side_message(ClientSideMessage::JoinPreparedGame {
-                    reservation: res_clone,
-                })
+                self.send_client_side_message(ClientSideMessage::JoinPreparedGame { reservation })
             }
             None => self.send_client_side_message(ClientSideMessage::JoinAnyGame),
         }
     }
 
+    pub fn observe_game(&mut self, room_id: &str, password: &str) -> Result<usize, Error> {
+        self.send_client_side_message(ClientSideMessage::Authenticate {
+            password: String::from(password),
+        })?;
+        self.send_client_side_message(ClientSideMessage::Observe {
+            room_id: String::from(room_id),
+        })
+    }
+
+    pub fn prepare_game(
+        &mut self,
+        password: &str,
+        player_names: (String, String),
+    ) -> Result<usize, Error> {
+        self.send_client_side_message(ClientSideMessage::Authenticate {
+            password: String::from(password),
+        })?;
+        self.send_client_side_message(ClientSideMessage::Prepare { player_names })
+    }
+
+    pub fn wait_for_prepared_response(&mut self) -> Result<PreparedGame, Error> {
+        let condition_function =
+            |string_buffer: &String| string_buffer.contains("</prepared>");
+        let response = self
+            .network_manager
+            .read_string_until_condition(&condition_function)?;
+
+        PreparedGame::parse(&response)
+    }
+
     pub fn wait_for_joined_response(&mut self) -> Result<String, Error> {
         let response = self.network_manager.read_string_exact(69)?; // nice
         if !response.starts_with("<protocol>") {
@@ -67,17 +86,31 @@ impl ProtocolManager {
         Ok(joined.room_id)
     }
 
-    pub fn get_next_message(&mut self) -> Result<ServerSideMessage, Error> {
+    fn receive_messages(&mut self) -> Result<(), Error> {
         let condition_function = |string_buffer: &String| {
-            return string_buffer.ends_with("</room>") || string_buffer.contains("<left roomId=\"");
+            string_buffer.ends_with("</room>") || string_buffer.contains("<left roomId=\"")
         };
         let text = self
             .network_manager
             .read_string_until_condition(&condition_function)?;
 
-        let text = format!("<received>{}</received>", text);
-        let received = from_str::<Received>(&text)?;
-        let server_side_message = ServerSideMessage::try_from(received)?;
-        Ok(server_side_message)
+        self.pending_messages
+            .extend(ServerSideMessage::parse_received(&text)?);
+
+        Ok(())
+    }
+
+    pub fn get_next_message(&mut self) -> Result<ServerSideMessage, Error> {
+        loop {
+            if let Some(server_side_message) = self.pending_messages.pop_front() {
+                return Ok(server_side_message);
+            }
+            self.receive_messages()?;
+        }
+    }
+
+    pub fn close(&mut self) -> Result<(), Error> {
+        self.send_client_side_message(ClientSideMessage::Exit)?;
+        self.network_manager.shutdown()
     }
 }
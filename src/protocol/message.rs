@@ -3,25 +3,37 @@ use std::convert::TryFrom;
 use crate::game::game_state::GameState;
 use crate::game::moves::Move;
 use crate::game::result::GameResult;
+use crate::util::coordinates::Coordinates;
 use crate::util::error::Error;
 use crate::xml::conversion::FromDeserializable;
 use crate::xml::enums::{DataClass, PlayerTeam};
-use crate::xml::server::data::Received;
+use crate::xml::serialization::{escape_attribute, from_str};
+use crate::xml::server::data::{Prepared, Received, Room};
 
 pub enum ClientSideMessage {
     JoinAnyGame,
     JoinPreparedGame { reservation: String },
     Move { sent_move: Move, room_id: String },
+    Authenticate { password: String },
+    Observe { room_id: String },
+    Prepare { player_names: (String, String) },
+    Exit,
 }
 
 #[derive(Debug)]
 pub enum ServerSideMessage {
-    Error,
+    Error {
+        message: String,
+        original_move: Option<Move>,
+    },
     WelcomeMessage {
         room_id: String,
         own_team: Option<PlayerTeam>,
     },
     Left,
+    Observed {
+        room_id: String,
+    },
     MoveRequest,
     Memento {
         game_state: GameState,
@@ -31,13 +43,44 @@ pub enum ServerSideMessage {
     },
 }
 
+/// The room the server prepared for a game, with one reservation code per
+/// player.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreparedGame {
+    pub room_id: String,
+    pub reservations: Vec<String>,
+}
+
+impl PreparedGame {
+    /// Parses the `<prepared>` response of the server, which may be surrounded
+    /// by other text.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let start = text.find("<prepared").ok_or_else(|| {
+            Error::SimpleError(String::from("Response didn't contain <prepared>!"))
+        })?;
+        let end = text.find("</prepared>").ok_or_else(|| {
+            Error::SimpleError(String::from("Response didn't contain </prepared>!"))
+        })? + "</prepared>".len();
+
+        let prepared = from_str::<Prepared>(&text[start..end])?;
+        Ok(Self {
+            room_id: prepared.room_id,
+            reservations: prepared
+                .reservations
+                .into_iter()
+                .map(|reservation| reservation.code)
+                .collect(),
+        })
+    }
+}
+
 impl ClientSideMessage {
     pub fn to_xml(&self) -> Result<String, Error> {
         match self {
             ClientSideMessage::JoinAnyGame => Ok(String::from("<protocol><join />")),
             ClientSideMessage::JoinPreparedGame { reservation } => Ok(format!(
                 "<protocol><joinPrepared reservationCode=\"{}\" />",
-                reservation
+                escape_attribute(reservation)
             )),
             ClientSideMessage::Move { sent_move, room_id } => {
                 let from = &sent_move.from;
@@ -48,23 +91,68 @@ impl ClientSideMessage {
 
                 Ok(format!(
                     "<room roomId=\"{}\"><data class=\"move\">{}{}</data></room>",
-                    room_id, from_declaration, to_declaration
+                    escape_attribute(room_id),
+                    from_declaration,
+                    to_declaration
                 ))
             }
+            ClientSideMessage::Authenticate { password } => Ok(format!(
+                "<protocol><authenticate password=\"{}\" />",
+                escape_attribute(password)
+            )),
+            ClientSideMessage::Observe { room_id } => {
+                Ok(format!("<observe roomId=\"{}\" />", escape_attribute(room_id)))
+            }
+            ClientSideMessage::Prepare { player_names } => {
+                let slot_declaration = |name: &str| {
+                    format!(
+                        "<slot displayName=\"{}\" canTimeout=\"true\" reserved=\"true\"/>",
+                        escape_attribute(name)
+                    )
+                };
+
+                Ok(format!(
+                    "<prepare displayName=\"{} vs. {}\" paused=\"false\">{}{}</prepare>",
+                    escape_attribute(&player_names.0),
+                    escape_attribute(&player_names.1),
+                    slot_declaration(&player_names.0),
+                    slot_declaration(&player_names.1)
+                ))
+            }
+            ClientSideMessage::Exit => Ok(String::from("</protocol>")),
         }
     }
 }
 
-impl TryFrom<Received> for ServerSideMessage {
-    type Error = Error;
+impl ServerSideMessage {
+    /// Parses the messages in a chunk of text received from the server, with
+    /// or without the surrounding `<protocol>` tags.
+    pub fn parse_received(text: &str) -> Result<Vec<Self>, Error> {
+        let text = text.replace("</protocol>", "").replace("<protocol>", "");
+        let text = format!("<received>{}</received>", text);
+        let received = from_str::<Received>(&text)?;
 
-    fn try_from(received: Received) -> Result<Self, Error> {
-        match received.left {
-            Some(_) => return Ok(ServerSideMessage::Left),
-            None => {}
+        let mut messages = Vec::new();
+        if let Some(observed) = received.observed {
+            messages.push(ServerSideMessage::Observed {
+                room_id: observed.room_id,
+            });
         }
+        for room in received.rooms.iter() {
+            messages.push(ServerSideMessage::try_from(room)?);
+        }
+        if received.left.is_some() {
+            messages.push(ServerSideMessage::Left);
+        }
+
+        Ok(messages)
+    }
+}
+
+impl TryFrom<&Room> for ServerSideMessage {
+    type Error = Error;
 
-        let room = &received.rooms[0];
+    fn try_from(room: &Room) -> Result<Self, Error> {
         let room_data = &room.data;
         match room_data.class {
             DataClass::WelcomeMessage => {
@@ -77,7 +165,7 @@ impl TryFrom<Received> for ServerSideMessage {
             }
             DataClass::Memento => {
                 let unwrapped_state = room_data.state.as_ref().unwrap();
-                let game_state_conversion_result = GameState::from_deserializable(&unwrapped_state);
+                let game_state_conversion_result = GameState::from_deserializable(unwrapped_state);
                 let game_state = game_state_conversion_result.unwrap();
                 Ok(ServerSideMessage::Memento { game_state })
             }
@@ -86,7 +174,26 @@ impl TryFrom<Received> for ServerSideMessage {
                 let result = GameResult::from_deserializable(room_data)?;
                 Ok(ServerSideMessage::Result { result })
             }
-            DataClass::Error => Ok(ServerSideMessage::Error),
+            DataClass::Error => {
+                let message = room_data
+                    .message
+                    .clone()
+                    .unwrap_or_else(|| String::from("<no message>"));
+                let original_move = room_data
+                    .original_message
+                    .as_ref()
+                    .and_then(|original| match (&original.from, &original.to) {
+                        (Some(from), Some(to)) => Some(Move {
+                            from: Coordinates::from(from),
+                            to: Coordinates::from(to),
+                        }),
+                        _ => None,
+                    });
+                Ok(ServerSideMessage::Error {
+                    message,
+                    original_move,
+                })
+            }
         }
     }
 }
@@ -1,2 +1,2 @@
-pub mod manager;
+pub(crate) mod manager;
 pub mod message;
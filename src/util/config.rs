@@ -0,0 +1,184 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::error::Error;
+
+/// The path of the configuration file that is loaded if no other path is given.
+pub const DEFAULT_CONFIG_PATH: &str = "socha.toml";
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoggingConfig {
+    pub level: String,
+    pub directory: String,
+    /// Whether to write a log file at all. Every run of the client writes to
+    /// its own file, so every game ends up in a separate file. Without a
+    /// file, messages are only logged to stderr as far as they are
+    /// duplicated there.
+    pub to_file: bool,
+    /// The level up to which messages are duplicated to stderr
+    /// (`none`, `error`, `warn`, `info`, `debug`, `trace` or `all`)
+    pub duplicate_to_stderr: String,
+    /// Rotate the log file when it exceeds this size
+    pub rotate_size_kb: Option<u64>,
+    /// The number of rotated log files that are kept
+    pub keep_files: usize,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: String::from("debug"),
+            directory: String::from("log/"),
+            to_file: true,
+            duplicate_to_stderr: String::from("all"),
+            rotate_size_kb: None,
+            keep_files: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct EngineConfig {
+    /// The time the server grants for every move
+    pub time_budget_ms: u64,
+    /// The part of the time budget that is kept free for network latency
+    pub time_margin_ms: u64,
+    pub hash_size_mb: usize,
+    /// Not used by the engine yet, see `warn_about_unused_settings`
+    pub threads: usize,
+    pub weights_path: Option<String>,
+    pub book_path: Option<String>,
+}
+
+impl EngineConfig {
+    /// The time a strategy may use for calculating a move.
+    pub fn move_time_ms(&self) -> u64 {
+        self.time_budget_ms.saturating_sub(self.time_margin_ms)
+    }
+
+    /// Logs a warning for every setting that differs from its default but
+    /// isn't supported by the engine yet.
+    pub fn warn_about_unused_settings(&self) {
+        let default = Self::default();
+        if self.threads != default.threads {
+            log::warn!(
+                "The engine is single-threaded, threads={} is ignored",
+                self.threads
+            );
+        }
+        if let Some(weights_path) = &self.weights_path {
+            log::warn!(
+                "The engine has no evaluation weights, weights_path={} is ignored",
+                weights_path
+            );
+        }
+        if let Some(book_path) = &self.book_path {
+            log::warn!(
+                "The engine has no opening book, book_path={} is ignored",
+                book_path
+            );
+        }
+    }
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            time_budget_ms: 2000,
+            time_margin_ms: 300,
+            hash_size_mb: 16,
+            threads: 1,
+            weights_path: None,
+            book_path: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub host: String,
+    pub port: i32,
+    pub strategy: String,
+    pub record_directory: Option<String>,
+    pub telemetry_path: Option<String>,
+    pub board_style: String,
+
+    pub logging: LoggingConfig,
+    pub engine: EngineConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            host: String::from("localhost"),
+            port: 13050,
+            strategy: String::from("random"),
+            record_directory: None,
+            telemetry_path: None,
+            board_style: String::from("auto"),
+            logging: LoggingConfig::default(),
+            engine: EngineConfig::default(),
+        }
+    }
+}
+
+impl Config {
+    pub fn from_toml(text: &str) -> Result<Self, Error> {
+        match toml::from_str::<Config>(text) {
+            Ok(config) => Ok(config),
+            Err(error) => Err(Error::TomlError(error)),
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::from_toml(&text),
+            Err(error) => Err(Error::IOError(error)),
+        }
+    }
+
+    /// Loads the configuration file at the given path. Without a path, the
+    /// default configuration file is loaded if it exists.
+    pub fn load_or_default(path: Option<&str>) -> Result<Self, Error> {
+        match path {
+            Some(path) => Self::load(path),
+            None if Path::new(DEFAULT_CONFIG_PATH).exists() => Self::load(DEFAULT_CONFIG_PATH),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+impl Display for Config {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            fmt,
+            "host={} port={} strategy={} record_directory={:?} telemetry_path={:?} board_style={} log_level={} log_directory={} \
+             log_to_file={} log_duplicate_to_stderr={} log_rotate_size_kb={:?} log_keep_files={} \
+             time_budget_ms={} time_margin_ms={} hash_size_mb={} threads={} weights_path={:?} book_path={:?}",
+            self.host,
+            self.port,
+            self.strategy,
+            self.record_directory,
+            self.telemetry_path,
+            self.board_style,
+            self.logging.level,
+            self.logging.directory,
+            self.logging.to_file,
+            self.logging.duplicate_to_stderr,
+            self.logging.rotate_size_kb,
+            self.logging.keep_files,
+            self.engine.time_budget_ms,
+            self.engine.time_margin_ms,
+            self.engine.hash_size_mb,
+            self.engine.threads,
+            self.engine.weights_path,
+            self.engine.book_path
+        )
+    }
+}
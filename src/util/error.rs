@@ -1,3 +1,4 @@
+use std::fmt::{Display, Formatter};
 use std::string::FromUtf8Error;
 use std::num::ParseIntError;
 
@@ -6,6 +7,7 @@ use flexi_logger::FlexiLoggerError;
 use quick_xml::DeError;
 
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum Error {
     ArgsError(ArgsError),
     IOError(std::io::Error),
@@ -13,5 +15,23 @@ pub enum Error {
     SimpleError(String),
     FromUtf8Error(FromUtf8Error),
     XmlDeserializeError(DeError),
-    LoggerError(FlexiLoggerError)
+    LoggerError(FlexiLoggerError),
+    TomlError(toml::de::Error),
+    JsonError(serde_json::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ArgsError(error) => write!(fmt, "Args error: {}", error),
+            Error::IOError(error) => write!(fmt, "IO error: {}", error),
+            Error::ParseIntError(error) => write!(fmt, "Parse int error: {}", error),
+            Error::SimpleError(message) => write!(fmt, "{}", message),
+            Error::FromUtf8Error(error) => write!(fmt, "UTF-8 error: {}", error),
+            Error::XmlDeserializeError(error) => write!(fmt, "XML deserialize error: {}", error),
+            Error::LoggerError(error) => write!(fmt, "Logger error: {}", error),
+            Error::TomlError(error) => write!(fmt, "TOML error: {}", error),
+            Error::JsonError(error) => write!(fmt, "JSON error: {}", error),
+        }
+    }
 }
@@ -1,22 +1,89 @@
-use flexi_logger::{Duplicate, FileSpec, Logger};
+use flexi_logger::{Cleanup, Criterion, Duplicate, FileSpec, Logger, Naming};
 
+use super::config::LoggingConfig;
 use super::error::Error;
 
-pub fn setup_logger() -> Result<(), Error> {
-    let file_spec = FileSpec::default().directory("log/");
-    let logger_with_str = Logger::try_with_str("debug");
-    match logger_with_str {
-        Ok(logger) => {
-            let start_result = logger
-                .log_to_file(file_spec)
-                .duplicate_to_stderr(Duplicate::All)
-                .start();
-
-            match start_result {
-                Ok(_) => Ok(()),
-                Err(error) => Err(Error::LoggerError(error)),
-            }
-        }
+fn parse_duplicate(duplicate: &str) -> Result<Duplicate, Error> {
+    match duplicate {
+        "none" => Ok(Duplicate::None),
+        "error" => Ok(Duplicate::Error),
+        "warn" => Ok(Duplicate::Warn),
+        "info" => Ok(Duplicate::Info),
+        "debug" => Ok(Duplicate::Debug),
+        "trace" => Ok(Duplicate::Trace),
+        "all" => Ok(Duplicate::All),
+        _ => Err(Error::SimpleError(format!(
+            "Invalid stderr duplication level '{}'",
+            duplicate
+        ))),
+    }
+}
+
+fn create_logger(logging_config: &LoggingConfig) -> Result<Logger, Error> {
+    match Logger::try_with_str(&logging_config.level) {
+        Ok(logger) => Ok(logger),
+        Err(error) => Err(Error::LoggerError(error)),
+    }
+}
+
+fn start_file_logger(logging_config: &LoggingConfig) -> Result<(), Error> {
+    let file_spec = FileSpec::default().directory(&logging_config.directory);
+    let duplicate = parse_duplicate(&logging_config.duplicate_to_stderr)?;
+
+    let mut logger = create_logger(logging_config)?
+        .log_to_file(file_spec)
+        .duplicate_to_stderr(duplicate);
+    if let Some(rotate_size_kb) = logging_config.rotate_size_kb {
+        logger = logger.rotate(
+            Criterion::Size(rotate_size_kb * 1024),
+            Naming::Numbers,
+            Cleanup::KeepLogFiles(logging_config.keep_files),
+        );
+    }
+
+    match logger.start() {
+        Ok(_) => Ok(()),
+        Err(error) => Err(Error::LoggerError(error)),
+    }
+}
+
+/// Logs the messages up to the stderr duplication level to stderr and
+/// nowhere else.
+fn start_duplicate_only_logger(logging_config: &LoggingConfig) -> Result<(), Error> {
+    let duplicate = parse_duplicate(&logging_config.duplicate_to_stderr)?;
+
+    let logger = create_logger(logging_config)?
+        .do_not_log()
+        .duplicate_to_stderr(duplicate);
+    match logger.start() {
+        Ok(_) => Ok(()),
+        Err(error) => Err(Error::LoggerError(error)),
+    }
+}
+
+fn start_stderr_logger(logging_config: &LoggingConfig) -> Result<(), Error> {
+    match create_logger(logging_config)?.log_to_stderr().start() {
+        Ok(_) => Ok(()),
         Err(error) => Err(Error::LoggerError(error)),
     }
 }
+
+/// Starts the logger. Without a log file, only the messages that would be
+/// duplicated to stderr are logged. If the log file can't be created (e.g.
+/// because the working directory is read-only), the client logs everything
+/// to stderr instead.
+pub fn setup_logger(logging_config: &LoggingConfig) -> Result<(), Error> {
+    if !logging_config.to_file {
+        return start_duplicate_only_logger(logging_config);
+    }
+
+    match start_file_logger(logging_config) {
+        Ok(_) => Ok(()),
+        Err(Error::LoggerError(error)) => {
+            start_stderr_logger(logging_config)?;
+            log::warn!("Couldn't log to a file, logging to stderr only: {}", error);
+            Ok(())
+        }
+        Err(error) => Err(error),
+    }
+}
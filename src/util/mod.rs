@@ -1,3 +1,4 @@
+pub mod config;
 pub mod error;
 pub mod coordinates;
-pub mod logger_setup;
\ No newline at end of file
+pub mod logger_setup;
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::util::error::Error;
 
+#[allow(dead_code)]
 pub trait ToSerializable<To>
 where
     To: Serialize,
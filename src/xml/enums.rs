@@ -1,11 +1,11 @@
 extern crate quick_xml;
 extern crate serde;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::util::coordinates::Coordinates;
 
-#[derive(Debug, PartialEq, Deserialize, Clone, Eq, PartialOrd, Ord)]
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum PlayerTeam {
     One,
@@ -37,6 +37,7 @@ impl PlayerTeam {
 
 #[derive(Debug, PartialEq, Deserialize, Clone)]
 #[serde(rename_all = "UPPERCASE")]
+#[allow(dead_code)]
 pub enum PieceColor {
     Blue = 1,
     Red = 2,
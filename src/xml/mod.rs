@@ -1,4 +1,5 @@
 pub mod server;
 pub mod conversion;
 pub mod enums;
-pub mod serialization;
\ No newline at end of file
+pub mod recording;
+pub mod serialization;
@@ -0,0 +1,54 @@
+extern crate quick_xml;
+extern crate serde;
+
+use serde::Deserialize;
+
+use crate::xml::enums::PlayerTeam;
+use crate::xml::server::state::{From, State, To};
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct PrincipalVariationEntry {
+    pub from: From,
+    pub to: To,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct PrincipalVariation {
+    #[serde(rename = "entry", default)]
+    pub entries: Vec<PrincipalVariationEntry>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct RecordedMove {
+    pub from: From,
+    pub to: To,
+
+    #[serde(rename = "timeUs")]
+    pub time_us: u64,
+
+    /// Only available when the strategy reported search information
+    pub depth: Option<u32>,
+    /// Only available when the strategy reported search information
+    pub score: Option<i32>,
+    /// Only available when the strategy reported search information
+    pub pv: Option<PrincipalVariation>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct RecordedTurn {
+    pub state: State,
+
+    /// Only available for turns in which the client sent a move
+    #[serde(rename = "move")]
+    pub sent_move: Option<RecordedMove>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Recording {
+    #[serde(rename = "roomId")]
+    pub room_id: String,
+    pub team: Option<PlayerTeam>,
+
+    #[serde(rename = "turn", default)]
+    pub turns: Vec<RecordedTurn>,
+}
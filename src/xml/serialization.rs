@@ -1,4 +1,5 @@
 use quick_xml::de::from_str as quick_xml_from_str;
+use quick_xml::escape::escape;
 use serde::de::DeserializeOwned;
 
 use crate::util::error::Error;
@@ -9,3 +10,9 @@ pub fn from_str<T: DeserializeOwned>(string: &str) -> Result<T, Error> {
         Err(error) => Err(Error::XmlDeserializeError(error)),
     }
 }
+
+/// Escapes `&`, `<`, `>`, `'` and `"`, so the text can be used as the value of
+/// an attribute.
+pub fn escape_attribute(value: &str) -> String {
+    String::from_utf8_lossy(&escape(value.as_bytes())).into_owned()
+}
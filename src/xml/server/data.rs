@@ -5,6 +5,7 @@ use serde::Deserialize;
 
 use crate::xml::enums::{DataClass, PlayerTeam};
 
+use super::error::OriginalMessage;
 use super::result::{Definition, Scores, Winner};
 use super::state::State;
 
@@ -24,6 +25,12 @@ pub struct Data {
     pub scores: Option<Scores>,
     /// Only available when `class` is equal to `result`
     pub winner: Option<Winner>,
+
+    /// Only available when `class` is equal to `error`
+    pub message: Option<String>,
+    /// Only available when `class` is equal to `error`
+    #[serde(rename = "originalMessage", alias = "originalRequest")]
+    pub original_message: Option<OriginalMessage>,
 }
 
 #[derive(Debug, Deserialize, PartialEq)]
@@ -40,10 +47,19 @@ pub struct Left {
     pub room_id: String,
 }
 
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Observed {
+    #[serde(rename = "roomId")]
+    pub room_id: String,
+}
+
 #[derive(Debug, Deserialize, PartialEq)]
 pub struct Received {
     pub left: Option<Left>,
 
+    /// Only available when observing a game
+    pub observed: Option<Observed>,
+
     #[serde(rename = "room", default)]
     pub rooms: Vec<Room>,
 }
@@ -53,3 +69,18 @@ pub struct Joined {
     #[serde(rename = "roomId")]
     pub room_id: String,
 }
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Reservation {
+    #[serde(rename = "$value")]
+    pub code: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Prepared {
+    #[serde(rename = "roomId")]
+    pub room_id: String,
+
+    #[serde(rename = "reservation", default)]
+    pub reservations: Vec<Reservation>,
+}
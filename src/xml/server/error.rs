@@ -0,0 +1,16 @@
+extern crate quick_xml;
+extern crate serde;
+
+use serde::Deserialize;
+
+use super::state::{From, To};
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct OriginalMessage {
+    pub class: Option<String>,
+
+    /// Only available when the original message was a move
+    pub from: Option<From>,
+    /// Only available when the original message was a move
+    pub to: Option<To>,
+}
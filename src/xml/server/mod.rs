@@ -1,3 +1,5 @@
 pub mod data;
+pub mod error;
+pub mod replay;
 pub mod result;
 pub mod state;
@@ -0,0 +1,24 @@
+extern crate quick_xml;
+extern crate serde;
+
+use serde::Deserialize;
+
+use crate::xml::recording::RecordedTurn;
+
+use super::data::Room;
+use super::state::State;
+
+/// A replay as saved by the server. Depending on the server version the
+/// states are stored either directly or wrapped in `memento` rooms. Game
+/// recordings written by the client store them inside `turn` elements.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Replay {
+    #[serde(rename = "state", default)]
+    pub states: Vec<State>,
+
+    #[serde(rename = "room", default)]
+    pub rooms: Vec<Room>,
+
+    #[serde(rename = "turn", default)]
+    pub turns: Vec<RecordedTurn>,
+}
@@ -0,0 +1,74 @@
+//! Prepares a game on a minimal mock server that answers `prepare` requests
+//! with reservation codes, like the Software Challenge server does.
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+use socha_2022_rust::logic::admin::prepare_game;
+use socha_2022_rust::protocol::message::PreparedGame;
+
+const PREPARED_RESPONSE: &str = "<protocol>\n  \
+     <prepared roomId=\"4c1ad9b0-2d5c-4d8e-a1f6-0b1e3c7d9a52\">\n    \
+     <reservation>3f2a6b1c</reservation>\n    \
+     <reservation>9e8d7c6b</reservation>\n  \
+     </prepared>";
+
+/// Accepts one client, answers its `prepare` request and returns everything
+/// the client sent until it closed the connection.
+fn spawn_mock_server() -> (i32, thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port() as i32;
+
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut received = Vec::new();
+        let mut buffer = [0; 256];
+        let mut answered = false;
+        loop {
+            let size = stream.read(&mut buffer).unwrap();
+            if size == 0 {
+                break;
+            }
+            received.extend_from_slice(&buffer[..size]);
+            if !answered && String::from_utf8_lossy(&received).contains("</prepare>") {
+                stream.write_all(PREPARED_RESPONSE.as_bytes()).unwrap();
+                answered = true;
+            }
+        }
+        String::from_utf8(received).unwrap()
+    });
+
+    (port, handle)
+}
+
+#[test]
+fn prepared_game_contains_the_reservations() {
+    let (port, server) = spawn_mock_server();
+
+    let prepared = prepare_game(
+        "127.0.0.1",
+        port,
+        "secret",
+        (String::from("Team <A>"), String::from("Team \"B\"")),
+    )
+    .unwrap();
+    let received = server.join().unwrap();
+
+    assert_eq!(
+        prepared,
+        PreparedGame {
+            room_id: String::from("4c1ad9b0-2d5c-4d8e-a1f6-0b1e3c7d9a52"),
+            reservations: vec![String::from("3f2a6b1c"), String::from("9e8d7c6b")],
+        }
+    );
+    assert!(received.starts_with("<protocol><authenticate password=\"secret\" />"));
+    assert!(received.contains("<prepare displayName=\"Team &lt;A&gt; vs. Team &quot;B&quot;\""));
+    assert!(received.contains("<slot displayName=\"Team &lt;A&gt;\""));
+    assert!(received.ends_with("</protocol>"));
+}
+
+#[test]
+fn response_without_prepared_is_rejected() {
+    assert!(PreparedGame::parse("<protocol>\n<left roomId=\"x\"/>").is_err());
+}
@@ -0,0 +1,106 @@
+//! Checks the attack maps and threat queries of `GameState` on sample positions.
+
+mod common;
+
+use socha_2022_rust::game::notation::parse_square;
+use socha_2022_rust::Coordinates;
+use socha_2022_rust::PlayerTeam;
+
+use common::position;
+
+fn square(text: &str) -> Coordinates {
+    parse_square(text).unwrap()
+}
+
+fn squares(texts: &[&str]) -> Vec<Coordinates> {
+    texts.iter().map(|text| square(text)).collect()
+}
+
+#[test]
+fn attacked_squares_follow_the_piece_offsets() {
+    // Moewe of team one on d4, Seestern of team two on e4
+    let game_state = position("8/8/8/3Ms3/8/8/8/8 1 0 0:0");
+
+    assert_eq!(
+        game_state.attacked_squares(&PlayerTeam::One),
+        squares(&["d3", "c4", "e4", "d5"])
+    );
+    assert_eq!(
+        game_state.attacked_squares(&PlayerTeam::Two),
+        squares(&["d3", "f3", "d4", "d5", "f5"])
+    );
+}
+
+#[test]
+fn attacked_squares_depend_on_the_team() {
+    // Herzmuscheln only attack forwards, towards the opponent's start line
+    let team_one = position("8/8/8/3H4/8/8/8/8 1 0 0:0");
+    let team_two = position("8/8/8/3h4/8/8/8/8 1 0 0:0");
+
+    assert_eq!(team_one.attacked_squares(&PlayerTeam::One), squares(&["e3", "e5"]));
+    assert_eq!(team_two.attacked_squares(&PlayerTeam::Two), squares(&["c3", "c5"]));
+}
+
+#[test]
+fn attacks_stay_on_the_board() {
+    let game_state = position("R7/8/8/8/8/8/8/8 1 0 0:0");
+
+    assert_eq!(game_state.attacked_squares(&PlayerTeam::One), squares(&["c2", "b3"]));
+}
+
+#[test]
+fn attack_map_counts_attackers() {
+    // Moewe on d4 and Herzmuschel on c2 both attack d3
+    let game_state = position("8/2H5/8/3M4/8/8/8/8 1 0 0:0");
+    let attack_map = game_state.attack_map(&PlayerTeam::One);
+
+    assert_eq!(attack_map.attack_count(&square("d3")), 2);
+    assert_eq!(attack_map.attack_count(&square("d1")), 1);
+    assert_eq!(attack_map.attack_count(&square("a1")), 0);
+    assert!(!attack_map.is_attacked(&Coordinates::new(-1, 0)));
+}
+
+#[test]
+fn attackers_of_includes_both_teams() {
+    let game_state = position("8/8/8/3Ms3/8/8/8/8 1 0 0:0");
+
+    assert_eq!(game_state.attackers_of(&square("d3")), squares(&["d4", "e4"]));
+    assert_eq!(game_state.attackers_of(&square("e4")), squares(&["d4"]));
+    assert_eq!(
+        game_state.attackers_of_team(&square("d3"), &PlayerTeam::Two),
+        squares(&["e4"])
+    );
+    assert!(game_state.attackers_of(&square("h8")).is_empty());
+}
+
+#[test]
+fn pieces_are_defended_by_their_own_team() {
+    // The Herzmuschel on c3 defends the Moewe on d4
+    let defended = position("8/8/2H5/3Ms3/8/8/8/8 1 0 0:0");
+    let undefended = position("8/8/8/3Ms3/8/8/8/8 1 0 0:0");
+
+    assert!(defended.is_defended(&square("d4")));
+    assert!(!undefended.is_defended(&square("d4")));
+    assert!(!defended.is_defended(&square("e4")));
+    assert!(!defended.is_defended(&square("a1")));
+}
+
+#[test]
+fn threatened_and_hanging_pieces() {
+    let defended = position("8/8/2H5/3Ms3/8/8/8/8 1 0 0:0");
+    let undefended = position("8/8/8/3Ms3/8/8/8/8 1 0 0:0");
+
+    assert_eq!(defended.threatened_pieces(&PlayerTeam::One), squares(&["d4"]));
+    assert_eq!(defended.threatened_pieces(&PlayerTeam::Two), squares(&["e4"]));
+    assert!(defended.hanging_pieces(&PlayerTeam::One).is_empty());
+    assert_eq!(defended.hanging_pieces(&PlayerTeam::Two), squares(&["e4"]));
+    assert_eq!(undefended.hanging_pieces(&PlayerTeam::One), squares(&["d4"]));
+}
+
+#[test]
+fn start_position_has_no_threats() {
+    let game_state = position("R6r/H6s/M6m/S6h/H6s/M6m/S6h/R6r 1 0 0:0");
+
+    assert!(game_state.threatened_pieces(&PlayerTeam::One).is_empty());
+    assert!(game_state.threatened_pieces(&PlayerTeam::Two).is_empty());
+}
@@ -0,0 +1,60 @@
+//! Runs the client binary to check how subcommand arguments are parsed.
+
+use std::process::{Command, Output};
+
+const POSITION: &str = "8/8/2H5/3Ms*3/5h2/8/8/R6r 1 4 1:0";
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_socha_2022_rust"))
+        .args(["--no-log-file", "--board-style", "ascii"])
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn last_stdout_line(output: &Output) -> String {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout.lines().last().unwrap_or_default().to_string()
+}
+
+#[test]
+fn position_is_taken_before_and_after_options() {
+    for args in [
+        ["perft", POSITION, "--depth", "1"],
+        ["perft", "--depth", "1", POSITION],
+    ]
+    .iter()
+    {
+        let output = run(args);
+        assert!(output.status.success(), "{:?} failed", args);
+        assert!(
+            last_stdout_line(&output).starts_with("Depth 1: 7 nodes"),
+            "{:?} printed {}",
+            args,
+            last_stdout_line(&output)
+        );
+    }
+
+    let output = run(&["perft", "--depth", "1"]);
+    assert!(last_stdout_line(&output).starts_with("Depth 1: 16 nodes"));
+}
+
+#[test]
+fn extra_arguments_are_rejected() {
+    assert!(!run(&["perft", "--depth", "1", POSITION, "extra"])
+        .status
+        .success());
+    assert!(!run(&["bench", "extra"]).status.success());
+}
+
+#[test]
+fn stderr_level_applies_without_a_log_file() {
+    let stderr = |level: &str| {
+        let output = run(&["--log-stderr", level, "perft", "--depth", "1"]);
+        String::from_utf8_lossy(&output.stderr).into_owned()
+    };
+
+    assert_eq!(stderr("none"), "");
+    assert!(!stderr("warn").contains("INFO"));
+    assert!(stderr("info").contains("INFO"));
+}
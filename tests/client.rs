@@ -0,0 +1,94 @@
+//! Plays a game on a minimal mock server that asks for a move in a position
+//! without a legal move.
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use socha_2022_rust::game::notation::parse_position;
+use socha_2022_rust::logic::{client::play_game, logic::Logic};
+
+const ROOM_ID: &str = "0c5b1a7e-6f0d-4a4e-9d4b-2e8f3a1c7b90";
+/// Team One is to move, but none of its pieces can move.
+const NO_LEGAL_MOVES: &str = "8/7H/6H1/7H/8/8/8/r7 1 4 0:1";
+
+fn server_messages() -> (String, String) {
+    let joined = format!("<protocol>\n  <joined roomId=\"{}\"/>\n", ROOM_ID);
+    let game_state = parse_position(NO_LEGAL_MOVES).unwrap();
+    let rooms = format!(
+        "<room roomId=\"{0}\"><data class=\"welcomeMessage\" color=\"ONE\"/></room>\
+         <room roomId=\"{0}\"><data class=\"memento\">{1}</data></room>\
+         <room roomId=\"{0}\"><data class=\"moveRequest\"/></room>",
+        ROOM_ID,
+        game_state.to_xml()
+    );
+    (joined, rooms)
+}
+
+/// Accepts one client, answers its join request with the messages and returns
+/// everything the client sent until it closed the connection.
+fn spawn_mock_server() -> (i32, thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port() as i32;
+    let (joined, rooms) = server_messages();
+
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut received = Vec::new();
+        let mut buffer = [0; 256];
+        let mut answered = false;
+        loop {
+            let size = stream.read(&mut buffer).unwrap();
+            if size == 0 {
+                break;
+            }
+            received.extend_from_slice(&buffer[..size]);
+            if !answered && String::from_utf8_lossy(&received).contains("<join") {
+                stream.write_all(joined.as_bytes()).unwrap();
+                stream.write_all(rooms.as_bytes()).unwrap();
+                answered = true;
+            }
+        }
+        String::from_utf8(received).unwrap()
+    });
+
+    (port, handle)
+}
+
+#[test]
+fn move_request_without_legal_moves_ends_the_game() {
+    assert_eq!(server_messages().0.len(), 69);
+    let (port, server) = spawn_mock_server();
+    let directory = std::env::temp_dir().join(format!("socha-client-{}", std::process::id()));
+
+    // A client that keeps waiting for messages would block the test forever
+    let (sender, receiver) = mpsc::channel();
+    let recording_directory = directory.clone();
+    thread::spawn(move || {
+        let mut logic = Logic::new();
+        logic.enable_recording(recording_directory);
+        sender
+            .send(play_game("127.0.0.1", port, None, logic))
+            .unwrap()
+    });
+    receiver
+        .recv_timeout(Duration::from_secs(10))
+        .expect("the client didn't end the game")
+        .unwrap();
+    let received = server.join().unwrap();
+
+    let recording_saved = directory.join(format!("{}.xml", ROOM_ID)).exists();
+    let history_saved = directory.join(format!("{}.txt", ROOM_ID)).exists();
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    assert!(!received.contains("<move"), "sent a move: {}", received);
+    assert!(
+        received.ends_with("</protocol>"),
+        "didn't exit: {}",
+        received
+    );
+    assert!(recording_saved);
+    assert!(history_saved);
+}
@@ -0,0 +1,19 @@
+//! Helpers shared by the integration tests. Not every test uses every
+//! helper.
+#![allow(dead_code)]
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use socha_2022_rust::game::{game_state::GameState, notation::parse_position};
+use socha_2022_rust::logic::selfplay;
+
+/// Parses a position in notation, failing the test if it is invalid.
+pub fn position(text: &str) -> GameState {
+    parse_position(text).unwrap()
+}
+
+/// The game states of a random game. The same seed always gives the same
+/// game, so print the seed when an assertion about the game fails.
+pub fn random_game_positions(seed: u64) -> Vec<GameState> {
+    selfplay::random_game_positions(&mut StdRng::seed_from_u64(seed)).unwrap()
+}
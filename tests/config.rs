@@ -0,0 +1,17 @@
+//! Parses configuration files.
+
+use socha_2022_rust::config::Config;
+
+#[test]
+fn example_config_parses_to_the_defaults() {
+    let config = Config::from_toml(include_str!("../socha.example.toml")).unwrap();
+    assert_eq!(config, Config::default());
+}
+
+#[test]
+fn unknown_fields_are_rejected() {
+    assert!(Config::from_toml("hots = \"localhost\"").is_err());
+    assert!(Config::from_toml("[logging]\nlevle = \"info\"").is_err());
+    assert!(Config::from_toml("[engine]\nhash_mb = 32").is_err());
+    assert!(Config::from_toml("[engine]\nhash_size_mb = 32").is_ok());
+}
@@ -0,0 +1,90 @@
+//! Compares received game states with the ones predicted by our rules.
+
+use socha_2022_rust::game::{
+    game_state::GameState,
+    notation::{parse_move, parse_position},
+};
+use socha_2022_rust::logic::crosscheck::{cross_check, diff_game_states};
+use socha_2022_rust::Coordinates;
+
+const PREVIOUS: &str = "8/8/2H5/3Ms*3/5h2/8/8/R6r 1 4 1:0";
+
+/// The game state after the move, as the server would send it.
+fn received_after(previous: &GameState, text: &str) -> GameState {
+    let mut received = previous.clone();
+    received.perform_move(&parse_move(text).unwrap()).unwrap();
+    received
+}
+
+#[test]
+fn matching_states_have_no_divergence() {
+    let previous = parse_position(PREVIOUS).unwrap();
+    let received = received_after(&previous, "d4-e4");
+
+    assert!(cross_check(&previous, &received).is_none());
+    assert!(diff_game_states(&received, &received).is_empty());
+}
+
+#[test]
+fn board_divergence_lists_the_square() {
+    let previous = parse_position(PREVIOUS).unwrap();
+    let mut received = received_after(&previous, "d4-e4");
+    received.board.pieces.remove(&Coordinates::new(0, 7));
+
+    let divergence = cross_check(&previous, &received).unwrap();
+
+    assert_eq!(divergence.server_move, parse_move("d4-e4").unwrap());
+    assert!(divergence.predicted_state.is_some());
+    assert_eq!(
+        divergence.differences,
+        vec![String::from("a8: predicted R, received empty")]
+    );
+}
+
+#[test]
+fn amber_and_turn_divergences_are_listed() {
+    let previous = parse_position(PREVIOUS).unwrap();
+    let predicted = received_after(&previous, "d4-e4");
+    let mut received = predicted.clone();
+    received.ambers = (0, 1);
+
+    let divergence = cross_check(&previous, &received).unwrap();
+    assert_eq!(divergence.differences.len(), 1);
+    assert!(divergence.differences[0].starts_with("ambers: predicted"));
+
+    received.turn += 1;
+    let differences = diff_game_states(&predicted, &received);
+    assert_eq!(differences.len(), 2);
+    assert_eq!(
+        differences[0],
+        format!(
+            "turn: predicted {}, received {}",
+            predicted.turn, received.turn
+        )
+    );
+}
+
+#[test]
+fn rejected_move_is_reported() {
+    let previous = parse_position(PREVIOUS).unwrap();
+    let mut received = previous.clone();
+    received.turn += 1;
+    // The piece on h8 belongs to the team that isn't on turn
+    received.last_move = Some(parse_move("h8-g8").unwrap());
+
+    let divergence = cross_check(&previous, &received).unwrap();
+
+    assert!(divergence.predicted_state.is_none());
+    assert_eq!(divergence.differences.len(), 1);
+    assert!(divergence.differences[0].starts_with("our rules rejected the move"));
+}
+
+#[test]
+fn states_that_dont_follow_each_other_are_skipped() {
+    let previous = parse_position(PREVIOUS).unwrap();
+    let mut received = received_after(&previous, "d4-e4");
+    received.turn += 1;
+    received.ambers = (0, 1);
+
+    assert!(cross_check(&previous, &received).is_none());
+}
@@ -0,0 +1,33 @@
+//! Draws board diagrams as SVG.
+
+use socha_2022_rust::game::diagram::{render_svg, DiagramOptions};
+use socha_2022_rust::game::notation::{parse_move, parse_position};
+
+#[test]
+fn arrows_are_drawn_for_moves() {
+    let mut game_state = parse_position("8/8/8/3Ms*3/8/8/8/R6r 1 0 1:0").unwrap();
+    game_state.last_move = Some(parse_move("a1-b1").unwrap());
+    let options = DiagramOptions {
+        candidate_moves: vec![parse_move("d4-e4").unwrap()],
+        ..DiagramOptions::default()
+    };
+
+    let svg = render_svg(&game_state, &options);
+
+    assert_eq!(svg.matches("<line ").count(), 2);
+}
+
+#[test]
+fn zero_length_arrows_are_skipped() {
+    let mut game_state = parse_position("8/8/8/3Ms*3/8/8/8/R6r 1 0 1:0").unwrap();
+    game_state.last_move = Some(parse_move("a1-a1").unwrap());
+    let options = DiagramOptions {
+        candidate_moves: vec![parse_move("d4-d4").unwrap()],
+        ..DiagramOptions::default()
+    };
+
+    let svg = render_svg(&game_state, &options);
+
+    assert!(!svg.contains("NaN"));
+    assert!(!svg.contains("<line "));
+}
@@ -0,0 +1,153 @@
+//! Checks the game history, position hashes and how the search treats
+//! repeated positions.
+
+use socha_2022_rust::game::{
+    game_state::GameState,
+    history::GameHistory,
+    notation::{parse_move, parse_position},
+    zobrist::position_hash,
+};
+use socha_2022_rust::logic::search::{Search, SearchLimits, WIN_SCORE};
+use socha_2022_rust::PlayerTeam;
+
+const START: &str = "8/8/2H5/3Ms*3/5h2/8/8/R6r 1 4 1:0";
+
+fn after_moves(game_state: &GameState, moves: &[&str]) -> GameState {
+    let mut game_state = game_state.clone();
+    for r#move in moves.iter() {
+        game_state
+            .perform_move(&parse_move(r#move).unwrap())
+            .unwrap();
+    }
+    game_state
+}
+
+/// The game states of a game from the start position with the given moves.
+fn game_states(moves: &[&str]) -> Vec<GameState> {
+    let mut game_states = vec![parse_position(START).unwrap()];
+    for r#move in moves.iter() {
+        let next = after_moves(game_states.last().unwrap(), &[r#move]);
+        game_states.push(next);
+    }
+    game_states
+}
+
+#[test]
+fn received_state_replaces_the_recorded_move() {
+    let game_states = game_states(&["d4-d3"]);
+    let mut history = GameHistory::new();
+    history.push_state(game_states[0].clone());
+    history.record_move(parse_move("a8-b6").unwrap());
+    assert_eq!(history.last_move(), Some(&parse_move("a8-b6").unwrap()));
+
+    history.push_state(game_states[1].clone());
+
+    assert_eq!(
+        history.entries()[0].played_move,
+        Some(parse_move("d4-d3").unwrap())
+    );
+    assert_eq!(history.entries()[1].played_move, None);
+    assert_eq!(history.last_move(), Some(&parse_move("d4-d3").unwrap()));
+}
+
+#[test]
+fn states_that_dont_follow_each_other_keep_the_recorded_move() {
+    let game_states = game_states(&["d4-d3", "h8-g6"]);
+    let mut history = GameHistory::new();
+    history.push_state(game_states[0].clone());
+    history.record_move(parse_move("d4-d3").unwrap());
+
+    history.push_state(game_states[2].clone());
+
+    assert_eq!(
+        history.entries()[0].played_move,
+        Some(parse_move("d4-d3").unwrap())
+    );
+}
+
+#[test]
+fn moves_are_split_by_team() {
+    let history = GameHistory::from_game_states(&game_states(&["d4-d3", "h8-g6", "a8-b6"]));
+
+    assert_eq!(history.moves().len(), 3);
+    assert_eq!(
+        history.moves_of(&PlayerTeam::One),
+        vec![&parse_move("d4-d3").unwrap(), &parse_move("a8-b6").unwrap()]
+    );
+    assert_eq!(
+        history.moves_of(&PlayerTeam::Two),
+        vec![&parse_move("h8-g6").unwrap()]
+    );
+}
+
+#[test]
+fn history_is_exported_as_text() {
+    let game_states = game_states(&["d4-d3", "h8-g6", "a8-b6"]);
+    let history = GameHistory::from_game_states(&game_states);
+
+    let text = history.to_text();
+    let lines = text.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines[0], START);
+    assert_eq!(lines[1], "3. d4-d3 h8-g6");
+    assert_eq!(lines[2], "4. a8-b6");
+    assert!(lines[3].starts_with("Final position: "));
+    assert_eq!(lines[4], "Result: -");
+    assert_eq!(GameHistory::new().to_text(), "");
+}
+
+#[test]
+fn transpositions_have_the_same_hash() {
+    let start = parse_position(START).unwrap();
+    let first = after_moves(&start, &["a8-b6", "h8-g6", "d4-d3"]);
+    let second = after_moves(&start, &["d4-d3", "h8-g6", "a8-b6"]);
+
+    assert_eq!(position_hash(&first), position_hash(&second));
+
+    let history = GameHistory::from_game_states(&[start.clone(), first.clone()]);
+    assert_eq!(history.repetitions(&second), 1);
+}
+
+#[test]
+fn hash_ignores_the_turn_but_not_the_team_or_ambers() {
+    let start = parse_position(START).unwrap();
+
+    let mut later = start.clone();
+    later.turn += 2;
+    assert_eq!(position_hash(&start), position_hash(&later));
+
+    let mut other_team = start.clone();
+    other_team.turn += 1;
+    assert_ne!(position_hash(&start), position_hash(&other_team));
+
+    let mut more_ambers = start.clone();
+    more_ambers.ambers = (1, 1);
+    assert_ne!(position_hash(&start), position_hash(&more_ambers));
+}
+
+#[test]
+fn repeated_won_position_keeps_its_score() {
+    // Team one has its second amber, every move of team two ends the round
+    // with a loss
+    let root = parse_position("8/8/8/8/8/8/8/R6r 1 1 2:0 d4-e4").unwrap();
+    let mut game_states = root
+        .legal_moves()
+        .map(|r#move| {
+            let mut child = root.clone();
+            child.perform_move(&r#move).unwrap();
+            child
+        })
+        .collect::<Vec<_>>();
+    game_states.push(root.clone());
+    let history = GameHistory::from_game_states(&game_states);
+
+    let report = Search::new(SearchLimits {
+        max_depth: Some(1),
+        move_time: None,
+    })
+    .with_history(&history)
+    .run(&root, 1, &mut |_| {})
+    .unwrap();
+
+    assert!(report.root_moves[0].score <= -(WIN_SCORE - 1));
+}
@@ -0,0 +1,91 @@
+//! Checks that the lazy legal move generator produces the same moves as
+//! `calculate_possible_moves`.
+
+mod common;
+
+use socha_2022_rust::game::{
+    game_state::GameState,
+    moves::Move,
+    notation::{format_move, format_position, parse_position},
+};
+
+use common::random_game_positions;
+
+const FIXTURES: &str = include_str!("fixtures/rules.txt");
+const RANDOM_GAMES: u64 = 20;
+
+/// The positions before and after the moves of the rules fixtures.
+fn fixture_positions() -> Vec<GameState> {
+    FIXTURES
+        .lines()
+        .filter_map(|line| {
+            line.strip_prefix("position:")
+                .or_else(|| line.strip_prefix("after:"))
+        })
+        .map(str::trim)
+        .filter(|position| *position != "error")
+        .map(|position| parse_position(position).unwrap())
+        .collect()
+}
+
+fn sorted_notations(moves: impl Iterator<Item = Move>) -> Vec<String> {
+    let mut notations = moves
+        .map(|r#move| format_move(&r#move))
+        .collect::<Vec<String>>();
+    notations.sort_unstable();
+    notations
+}
+
+fn assert_generators_agree(position: &GameState, origin: &str) {
+    let possible_moves = position.calculate_possible_moves(&position.get_current_team());
+    let expected_captures = possible_moves
+        .iter()
+        .filter(|r#move| position.board.get_piece_at(&r#move.to).is_some())
+        .cloned()
+        .collect::<Vec<Move>>();
+
+    assert_eq!(
+        sorted_notations(possible_moves.into_iter()),
+        sorted_notations(position.legal_moves()),
+        "legal moves of {} ({})",
+        format_position(position),
+        origin
+    );
+    assert_eq!(
+        sorted_notations(expected_captures.into_iter()),
+        sorted_notations(position.legal_captures()),
+        "legal captures of {} ({})",
+        format_position(position),
+        origin
+    );
+}
+
+#[test]
+fn generators_agree_on_random_games() {
+    for seed in 0..RANDOM_GAMES {
+        for position in random_game_positions(seed).iter() {
+            assert_generators_agree(position, &format!("random game with seed {}", seed));
+        }
+    }
+}
+
+#[test]
+fn generators_agree_on_the_rules_fixtures() {
+    let positions = fixture_positions();
+    assert!(!positions.is_empty());
+
+    for position in positions.iter() {
+        assert_generators_agree(position, "rules fixture");
+    }
+}
+
+#[test]
+fn random_games_are_reproducible() {
+    let notations = |seed| {
+        random_game_positions(seed)
+            .iter()
+            .map(format_position)
+            .collect::<Vec<String>>()
+    };
+    assert_eq!(notations(7), notations(7));
+}
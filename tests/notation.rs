@@ -0,0 +1,68 @@
+//! Formats and parses annotated moves like `R b3xc5+`.
+
+mod common;
+
+use socha_2022_rust::game::notation::{format_move_in, parse_annotated_move};
+
+use common::position;
+
+/// Positions with quiet moves, captures, stacks and ambers for both teams.
+const POSITIONS: [&str; 5] = [
+    "8/8/2H5/3Ms*3/5h2/8/8/R6r 1 4 1:0",
+    "8/8/2H5/3Ms*3/5h2/8/8/R6r 1 5 1:0",
+    "6H1/7s/8/3M*s3/8/8/8/R6r 1 0 0:0",
+    "7s/5R2/8/8/8/8/8/R6r 1 0 0:0",
+    "8/1S6/8/3Ms3/5h2/8/6m1/R6r 1 55 0:1",
+];
+
+#[test]
+fn annotated_moves_round_trip() {
+    for text in POSITIONS.iter() {
+        let game_state = position(text);
+        let moves = game_state.legal_moves().collect::<Vec<_>>();
+        assert!(!moves.is_empty(), "{}", text);
+
+        for r#move in moves.iter() {
+            let notation = format_move_in(&game_state, r#move);
+            let parsed = parse_annotated_move(&notation, &game_state)
+                .unwrap_or_else(|error| panic!("{} in {}: {}", notation, text, error));
+
+            assert_eq!(&parsed.r#move, r#move, "{} in {}", notation, text);
+            assert_eq!(Some(parsed), game_state.annotate_move(r#move));
+        }
+    }
+}
+
+#[test]
+fn annotations_cover_captures_stacks_and_ambers() {
+    let game_state = position("6H1/7s/8/3Ms3/8/8/8/R6r 1 0 0:0");
+
+    let notations = game_state
+        .legal_moves()
+        .map(|r#move| format_move_in(&game_state, &r#move))
+        .collect::<Vec<_>>();
+
+    assert!(notations.contains(&String::from("M d4xe4*")));
+    assert!(notations.contains(&String::from("H g1xh2+")));
+    assert!(notations.contains(&String::from("M d4-d3")));
+}
+
+#[test]
+fn mismatched_annotations_are_rejected() {
+    let game_state = position("6H1/7s/8/3Ms3/8/8/8/R6r 1 0 0:0");
+
+    // The piece on d4 is a Moewe
+    assert!(parse_annotated_move("S d4xe4*", &game_state).is_err());
+    // The move captures
+    assert!(parse_annotated_move("M d4-e4*", &game_state).is_err());
+    // The move doesn't score an amber
+    assert!(parse_annotated_move("M d4xe4*+", &game_state).is_err());
+    // The move doesn't capture
+    assert!(parse_annotated_move("M d4xd3", &game_state).is_err());
+    // The capture onto the start line scores instead of stacking
+    assert!(parse_annotated_move("H g1xh2*", &game_state).is_err());
+
+    assert!(parse_annotated_move("M d4xe4*", &game_state).is_ok());
+    // Moves without annotations aren't checked
+    assert!(parse_annotated_move("d4-e4", &game_state).is_ok());
+}
@@ -0,0 +1,97 @@
+//! Checks make/unmake and the proof-number search against a full minimax on
+//! positions shortly before the round limit.
+
+mod common;
+
+use socha_2022_rust::game::{
+    game_state::{GameState, GameStateResult},
+    notation::{format_position, parse_move},
+};
+use socha_2022_rust::logic::proof::{ProofLimits, ProofResult, ProofSearch};
+
+use common::position;
+
+/// The result for the team to move: 1 for a win, 0 for a draw, -1 for a loss.
+fn minimax(game_state: &mut GameState) -> i32 {
+    match game_state.get_result() {
+        GameStateResult::Player(winner) if winner == game_state.get_current_team() => return 1,
+        GameStateResult::Player(_) => return -1,
+        GameStateResult::Draw => return 0,
+        GameStateResult::Nothing => {}
+    }
+
+    let moves = game_state.legal_moves().collect::<Vec<_>>();
+    let mut best = -1;
+    for r#move in moves.iter() {
+        let undo = game_state.make_move(r#move).unwrap();
+        best = best.max(-minimax(game_state));
+        game_state.unmake_move(undo);
+    }
+    best
+}
+
+fn prove(game_state: &GameState) -> ProofResult {
+    ProofSearch::new(ProofLimits::default()).solve(game_state)
+}
+
+/// Checks that the result matches the minimax and the move keeps it.
+fn check_against_minimax(text: &str) {
+    let mut game_state = position(text);
+    let expected = minimax(&mut game_state);
+
+    match prove(&game_state) {
+        ProofResult::Win(r#move) => {
+            assert_eq!(expected, 1, "{}", text);
+            game_state.perform_move(&r#move).unwrap();
+            assert_eq!(minimax(&mut game_state), -1, "{}", text);
+        }
+        ProofResult::Draw(r#move) => {
+            assert_eq!(expected, 0, "{}", text);
+            game_state.perform_move(&r#move).unwrap();
+            assert_eq!(minimax(&mut game_state), 0, "{}", text);
+        }
+        ProofResult::Loss => assert_eq!(expected, -1, "{}", text),
+        ProofResult::Unknown => panic!("Unproven: {}", text),
+    }
+}
+
+#[test]
+fn unmake_move_restores_the_position() {
+    let text = "8/8/2H5/3Ms*3/5h2/8/8/R6r 1 4 1:0";
+    let mut game_state = position(text);
+
+    for r#move in game_state.legal_moves().collect::<Vec<_>>() {
+        let undo = game_state.make_move(&r#move).unwrap();
+        for reply in game_state.legal_moves().collect::<Vec<_>>() {
+            let reply_undo = game_state.make_move(&reply).unwrap();
+            game_state.unmake_move(reply_undo);
+        }
+        game_state.unmake_move(undo);
+        assert_eq!(format_position(&game_state), text);
+    }
+}
+
+#[test]
+fn second_amber_is_proven_a_win() {
+    let game_state = position("8/8/8/3Ms*3/8/8/8/R6r 1 0 1:0");
+
+    assert_eq!(
+        prove(&game_state),
+        ProofResult::Win(parse_move("d4-e4").unwrap())
+    );
+}
+
+#[test]
+fn results_match_minimax() {
+    check_against_minimax("8/8/8/3Ms*3/8/8/3S*m3/R6r 1 56 1:1");
+    check_against_minimax("8/8/2H5/3Ms*3/5h2/8/8/R6r 1 54 1:0");
+    check_against_minimax("8/8/8/3M4/5h2/8/8/R6r 1 56 0:0");
+    check_against_minimax("8/1S6/8/3Ms3/5h2/8/6m1/R6r 1 55 0:1");
+}
+
+#[test]
+fn game_over_is_not_solved() {
+    let game_state = position("8/8/8/3Ms3/8/8/8/R6r 1 60 0:0");
+
+    assert_eq!(prove(&game_state), ProofResult::Unknown);
+}
@@ -0,0 +1,75 @@
+//! Checks the messages exchanged with the server.
+
+use socha_2022_rust::game::moves::Move;
+use socha_2022_rust::protocol::message::{ClientSideMessage, ServerSideMessage};
+use socha_2022_rust::Coordinates;
+
+/// An error room as sent by the server after an invalid move.
+const INVALID_MOVE_ERROR: &str = r#"<protocol>
+  <room roomId="c9f3ac30-3ba5-4b7a-9f11-2a4c1b0e2d9f">
+    <data class="error" message="Ungültiger Zug: Der Zug ist nicht möglich">
+      <originalMessage class="move">
+        <from x="0" y="1"/>
+        <to x="2" y="1"/>
+      </originalMessage>
+    </data>
+  </room>"#;
+
+/// An error room without an originating move.
+const TIMEOUT_ERROR: &str = r#"<room roomId="c9f3ac30-3ba5-4b7a-9f11-2a4c1b0e2d9f">
+    <data class="error" message="Zeitüberschreitung"/>
+  </room>"#;
+
+#[test]
+fn error_room_is_decoded_with_the_original_move() {
+    let messages = ServerSideMessage::parse_received(INVALID_MOVE_ERROR).unwrap();
+
+    match messages.as_slice() {
+        [ServerSideMessage::Error {
+            message,
+            original_move,
+        }] => {
+            assert_eq!(message, "Ungültiger Zug: Der Zug ist nicht möglich");
+            assert_eq!(
+                original_move,
+                &Some(Move {
+                    from: Coordinates::new(0, 1),
+                    to: Coordinates::new(2, 1),
+                })
+            );
+        }
+        other => panic!("Unexpected messages: {:?}", other),
+    }
+}
+
+#[test]
+fn error_room_without_a_move_is_decoded() {
+    let messages = ServerSideMessage::parse_received(TIMEOUT_ERROR).unwrap();
+
+    match messages.as_slice() {
+        [ServerSideMessage::Error {
+            message,
+            original_move: None,
+        }] => assert_eq!(message, "Zeitüberschreitung"),
+        other => panic!("Unexpected messages: {:?}", other),
+    }
+}
+
+#[test]
+fn attributes_of_client_messages_are_escaped() {
+    let authenticate = ClientSideMessage::Authenticate {
+        password: String::from("a\"b<c&d"),
+    };
+    let observe = ClientSideMessage::Observe {
+        room_id: String::from("room\" evil=\"1"),
+    };
+
+    assert_eq!(
+        authenticate.to_xml().unwrap(),
+        "<protocol><authenticate password=\"a&quot;b&lt;c&amp;d\" />"
+    );
+    assert_eq!(
+        observe.to_xml().unwrap(),
+        "<observe roomId=\"room&quot; evil=&quot;1\" />"
+    );
+}
@@ -0,0 +1,86 @@
+//! Reads game states written by `GameState::to_xml` back through the server
+//! message and recording decoders.
+
+use std::time::Duration;
+
+use socha_2022_rust::game::{
+    game_state::GameState,
+    notation::{format_position, parse_move, parse_position},
+};
+use socha_2022_rust::logic::recording::{load_recording, GameRecording, RecordedMove};
+use socha_2022_rust::logic::strategy::SearchInfo;
+use socha_2022_rust::protocol::message::ServerSideMessage;
+
+const POSITIONS: [&str; 3] = [
+    "8/8/2H5/3Ms*3/5h2/8/8/R6r 1 4 1:0",
+    "8/1S6/8/3Ms3/5h2/8/6m1/R6r 2 55 0:1",
+    "8/8/8/3Ms*3/8/8/3S*m3/R6r 1 56 1:1",
+];
+
+fn game_state(text: &str) -> GameState {
+    let mut game_state = parse_position(text).unwrap();
+    game_state.last_move = Some(parse_move("a1-b1").unwrap());
+    game_state
+}
+
+fn assert_same_state(decoded: &GameState, expected: &GameState) {
+    assert_eq!(format_position(decoded), format_position(expected));
+    assert_eq!(decoded.last_move, expected.last_move);
+}
+
+#[test]
+fn memento_state_round_trips() {
+    for text in POSITIONS.iter() {
+        let expected = game_state(text);
+        let room = format!(
+            "<room roomId=\"r\"><data class=\"memento\">{}</data></room>",
+            expected.to_xml()
+        );
+
+        match ServerSideMessage::parse_received(&room).unwrap().as_slice() {
+            [ServerSideMessage::Memento { game_state }] => assert_same_state(game_state, &expected),
+            other => panic!("Unexpected messages: {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn saved_recording_round_trips() {
+    let search_info = SearchInfo {
+        depth: 3,
+        score: -42,
+        principal_variation: vec![parse_move("d4-e4").unwrap(), parse_move("f3-g4").unwrap()],
+        ..SearchInfo::default()
+    };
+    let mut recording = GameRecording::new(String::from("../round \"trip\" & <test>"), None);
+    for (index, text) in POSITIONS.iter().enumerate() {
+        recording.record_game_state(game_state(text));
+        recording.record_move(RecordedMove {
+            sent_move: parse_move("d4-e4").unwrap(),
+            time_used: Duration::from_micros(1234),
+            search_info: if index == 0 {
+                Some(search_info.clone())
+            } else {
+                None
+            },
+        });
+    }
+
+    let directory = std::env::temp_dir().join(format!("socha-recording-{}", std::process::id()));
+    let path = recording.save(&directory).unwrap();
+    let loaded = load_recording(&path).unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    assert_eq!(path, directory.join("___round__trip_____test_.xml"));
+    assert_eq!(loaded.room_id, recording.room_id);
+    assert_eq!(loaded.turns.len(), POSITIONS.len());
+    for (loaded_turn, turn) in loaded.turns.iter().zip(recording.turns.iter()) {
+        assert_same_state(&loaded_turn.game_state, &turn.game_state);
+        let sent_move = loaded_turn.sent_move.as_ref().unwrap();
+        assert_eq!(sent_move.sent_move, parse_move("d4-e4").unwrap());
+        assert_eq!(sent_move.time_used, Duration::from_micros(1234));
+    }
+    let loaded_search_info = loaded.turns[0].sent_move.as_ref().unwrap().search_info.as_ref();
+    assert_eq!(loaded_search_info, Some(&search_info));
+    assert_eq!(loaded.turns[1].sent_move.as_ref().unwrap().search_info, None);
+}
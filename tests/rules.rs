@@ -0,0 +1,196 @@
+//! Checks `GameState::perform_move` and `GameState::get_result` against the
+//! hand-crafted positions in `tests/fixtures/rules.txt`.
+
+use socha_2022_rust::game::{
+    game_state::{GameEndReason, GameStateResult},
+    notation::{format_move_in, format_position, parse_annotated_move, parse_move, parse_position},
+};
+use socha_2022_rust::PlayerTeam;
+
+const FIXTURES: &str = include_str!("fixtures/rules.txt");
+
+#[derive(Debug, Default)]
+struct Fixture {
+    line: usize,
+    case: String,
+    position: String,
+    r#move: Option<String>,
+    after: Option<String>,
+    result: Option<String>,
+    reason: Option<String>,
+}
+
+fn parse_fixtures(text: &str) -> Vec<Fixture> {
+    let mut fixtures = Vec::new();
+    let mut current: Option<Fixture> = None;
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        if line.is_empty() {
+            fixtures.extend(current.take());
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once(':')
+            .unwrap_or_else(|| panic!("Line {}: expected 'key: value'", index + 1));
+        let fixture = current.get_or_insert_with(|| Fixture {
+            line: index + 1,
+            ..Fixture::default()
+        });
+        let value = value.trim().to_string();
+        match key.trim() {
+            "case" => fixture.case = value,
+            "position" => fixture.position = value,
+            "move" => fixture.r#move = Some(value),
+            "after" => fixture.after = Some(value),
+            "result" => fixture.result = Some(value),
+            "reason" => fixture.reason = Some(value),
+            key => panic!("Line {}: unknown key '{}'", index + 1, key),
+        }
+    }
+    fixtures.extend(current);
+
+    fixtures
+}
+
+fn parse_result(text: &str) -> GameStateResult {
+    match text {
+        "none" => GameStateResult::Nothing,
+        "one" => GameStateResult::Player(PlayerTeam::One),
+        "two" => GameStateResult::Player(PlayerTeam::Two),
+        "draw" => GameStateResult::Draw,
+        _ => panic!("Unknown result '{}'", text),
+    }
+}
+
+fn parse_reason(text: &str) -> Option<GameEndReason> {
+    match text {
+        "none" => None,
+        "ambers" => Some(GameEndReason::Ambers),
+        "round-limit" => Some(GameEndReason::RoundLimit),
+        "no-pieces" => Some(GameEndReason::NoPieces),
+        "no-legal-moves" => Some(GameEndReason::NoLegalMoves),
+        _ => panic!("Unknown reason '{}'", text),
+    }
+}
+
+fn check_fixture(fixture: &Fixture) -> Result<(), String> {
+    let mut game_state = parse_position(&fixture.position)
+        .map_err(|error| format!("invalid position: {}", error))?;
+
+    if let Some(text) = &fixture.r#move {
+        let r#move = parse_move(text).map_err(|error| format!("invalid move: {}", error))?;
+        let move_result = game_state.perform_move(&r#move);
+
+        match (fixture.after.as_deref(), move_result) {
+            (Some("error"), Ok(_)) => return Err(String::from("the move was accepted")),
+            (Some("error"), Err(_)) => return Ok(()),
+            (_, Err(error)) => return Err(format!("the move was rejected: {}", error)),
+            (Some(after), Ok(_)) => {
+                let expected = parse_position(after)
+                    .map_err(|error| format!("invalid expected position: {}", error))?;
+                let expected = format_position(&expected);
+                let actual = format_position(&game_state);
+                if expected != actual {
+                    return Err(format!("expected '{}', got '{}'", expected, actual));
+                }
+            }
+            (None, Ok(_)) => {}
+        }
+    }
+
+    if let Some(result) = &fixture.result {
+        let expected = parse_result(result);
+        let actual = game_state.get_result();
+        if expected != actual {
+            return Err(format!("expected result {:?}, got {:?}", expected, actual));
+        }
+    }
+
+    if let Some(reason) = &fixture.reason {
+        let expected = parse_reason(reason);
+        let actual = game_state.get_end_reason();
+        if expected != actual {
+            return Err(format!("expected reason {:?}, got {:?}", expected, actual));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn rules_conformance() {
+    let fixtures = parse_fixtures(FIXTURES);
+    assert!(!fixtures.is_empty());
+
+    let failures = fixtures
+        .iter()
+        .filter_map(|fixture| {
+            check_fixture(fixture).err().map(|error| {
+                format!("line {}: {}: {}", fixture.line, fixture.case, error)
+            })
+        })
+        .collect::<Vec<String>>();
+
+    assert!(
+        failures.is_empty(),
+        "{} of {} rules fixtures failed:\n{}",
+        failures.len(),
+        fixtures.len(),
+        failures.join("\n")
+    );
+}
+
+/// Checks the annotations of the accepted fixture moves against what
+/// `perform_move` did, and that `unmake_move` restores the position.
+fn check_annotations(fixture: &Fixture) -> Result<bool, String> {
+    let text = match (&fixture.r#move, fixture.after.as_deref()) {
+        (Some(text), Some(after)) if after != "error" => text,
+        _ => return Ok(false),
+    };
+    let mut game_state = parse_position(&fixture.position).map_err(|error| error.to_string())?;
+    let r#move = parse_move(text).map_err(|error| error.to_string())?;
+    let notation = format_move_in(&game_state, &r#move);
+    let annotated_move =
+        parse_annotated_move(&notation, &game_state).map_err(|error| error.to_string())?;
+    let before = format_position(&game_state);
+    let ambers_before = game_state.ambers;
+
+    let undo = game_state.make_move(&r#move).map_err(|error| error.to_string())?;
+    let scored = game_state.ambers != ambers_before;
+    let stacked = !annotated_move.piece.is_stacked()
+        && game_state
+            .board
+            .get_piece_at(&r#move.to)
+            .is_some_and(|piece| piece.is_stacked());
+    if annotated_move.scores_amber() != scored || annotated_move.makes_stack != stacked {
+        return Err(format!("'{}' doesn't describe the performed move", notation));
+    }
+
+    game_state.unmake_move(undo);
+    if format_position(&game_state) != before {
+        return Err(format!("unmaking '{}' didn't restore the position", notation));
+    }
+
+    Ok(annotated_move.scores_amber() || annotated_move.makes_stack)
+}
+
+#[test]
+fn annotations_match_the_stack_and_start_line_fixtures() {
+    let fixtures = parse_fixtures(FIXTURES);
+
+    let mut checked = 0;
+    for fixture in fixtures.iter() {
+        match check_annotations(fixture) {
+            Ok(true) => checked += 1,
+            Ok(false) => {}
+            Err(error) => panic!("line {}: {}: {}", fixture.line, fixture.case, error),
+        }
+    }
+
+    assert!(checked >= 5, "only {} fixtures stack or score", checked);
+}
@@ -0,0 +1,42 @@
+//! Checks the static exchange evaluation on sample positions. A piece is worth
+//! 100, a stack 150 and an amber 1000.
+
+use socha_2022_rust::game::notation::{parse_move, parse_position};
+use socha_2022_rust::logic::see::static_exchange_evaluation;
+
+fn see(position: &str, r#move: &str) -> i32 {
+    let game_state = parse_position(position).unwrap();
+    static_exchange_evaluation(&game_state, &parse_move(r#move).unwrap())
+}
+
+#[test]
+fn undefended_capture_wins_the_piece_and_a_stack() {
+    assert_eq!(see("8/8/8/3Ms3/8/8/8/R6r 1 0 0:0", "d4-e4"), 150);
+}
+
+#[test]
+fn recapturing_the_stack_scores_an_amber() {
+    // The Herzmuschel on f5 recaptures the new stack on e4
+    assert_eq!(see("8/8/8/3Ms3/5h2/8/8/R6r 1 0 0:0", "d4-e4"), 150 - 1050);
+}
+
+#[test]
+fn capturing_a_stack_scores_an_amber() {
+    assert_eq!(see("8/8/8/3Ms*3/5h2/8/8/R6r 1 0 0:0", "d4-e4"), 1000 + 150 - 100);
+}
+
+#[test]
+fn quiet_move_onto_an_attacked_square_loses_the_piece() {
+    assert_eq!(see("8/8/8/3M4/5h2/8/8/R6r 1 0 0:0", "d4-e4"), -150);
+}
+
+#[test]
+fn defended_square_deters_the_capture() {
+    // Capturing on e4 would let the Herzmuschel on d3 take the stack
+    assert_eq!(see("8/8/3H4/3M4/5h2/8/8/R6r 1 0 0:0", "d4-e4"), 0);
+}
+
+#[test]
+fn illegal_moves_evaluate_to_zero() {
+    assert_eq!(see("8/8/8/3Ms3/8/8/8/R6r 1 1 0:0", "d4-e4"), 0);
+}
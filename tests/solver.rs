@@ -0,0 +1,71 @@
+//! Checks the forced win solver on small puzzles.
+
+mod common;
+
+use socha_2022_rust::game::{
+    game_state::{GameState, GameStateResult},
+    moves::Move,
+    notation::parse_move,
+};
+use socha_2022_rust::logic::solver::{is_sharp, Solver, SolverResult};
+use socha_2022_rust::PlayerTeam;
+
+use common::position;
+
+fn solve(game_state: &GameState, plies: u32) -> Option<Vec<Move>> {
+    match Solver::new(plies).solve(game_state) {
+        SolverResult::Won(line) => Some(line),
+        SolverResult::NotFound => None,
+        SolverResult::Aborted => panic!("The solver has no limit to abort at"),
+    }
+}
+
+#[test]
+fn second_amber_wins_at_the_end_of_the_round() {
+    // Capturing the stack on e4 scores the second amber of team one
+    let game_state = position("8/8/8/3Ms*3/8/8/8/R6r 1 0 1:0");
+    let line = solve(&game_state, 3).unwrap();
+
+    assert_eq!(line.len(), 2);
+    assert_eq!(line[0], parse_move("d4-e4").unwrap());
+
+    let mut final_state = game_state.clone();
+    for r#move in line.iter() {
+        final_state.perform_move(r#move).unwrap();
+    }
+    assert_eq!(
+        final_state.get_result(),
+        GameStateResult::Player(PlayerTeam::One)
+    );
+}
+
+#[test]
+fn win_needs_enough_plies() {
+    // The round only ends after the reply of team two
+    let game_state = position("8/8/8/3Ms*3/8/8/8/R6r 1 0 1:0");
+
+    assert!(solve(&game_state, 1).is_none());
+}
+
+#[test]
+fn defence_that_scores_as_well_refutes_the_win() {
+    // Team two answers with a second amber of its own on d7
+    let game_state = position("8/8/8/3Ms*3/8/8/3S*m3/R6r 1 0 1:1");
+
+    assert!(solve(&game_state, 4).is_none());
+}
+
+#[test]
+fn start_position_has_no_forced_win() {
+    let game_state = position("R6r/H6s/M6m/S6h/H6s/M6m/S6h/R6r 1 0 0:0");
+
+    assert!(solve(&game_state, 3).is_none());
+    assert!(!is_sharp(&game_state));
+}
+
+#[test]
+fn positions_with_ambers_in_reach_are_sharp() {
+    assert!(is_sharp(&position("8/8/8/3Ms*3/8/8/8/R6r 1 0 0:0")));
+    assert!(is_sharp(&position("8/8/8/3Ms3/8/8/8/R6r 1 0 1:0")));
+    assert!(!is_sharp(&position("8/8/8/3Ms3/8/8/8/R6r 1 0 0:0")));
+}
@@ -0,0 +1,86 @@
+//! Reads search telemetry records and summarizes them.
+
+use std::time::Duration;
+
+use socha_2022_rust::game::notation::{parse_move, parse_position};
+use socha_2022_rust::logic::strategy::SearchInfo;
+use socha_2022_rust::logic::telemetry::{parse_telemetry, MoveTelemetry, TelemetrySummary};
+
+const RECORDS: &str = r#"{"room_id":"a","turn":0,"team":"ONE","ambers":[0,0],"legal_moves":10,"chosen_move":"a1-b1","time_us":1000,"depth":4,"nodes":2000,"nps":2000000,"score":5,"principal_variation":["a1-b1"]}
+
+{"room_id":"a","turn":2,"team":"ONE","ambers":[0,0],"legal_moves":20,"chosen_move":"b1-c1","time_us":3000,"depth":6,"nodes":9000,"nps":3000000,"score":-5,"principal_variation":[]}
+{"room_id":"b","turn":1,"team":"TWO","ambers":[0,0],"legal_moves":6,"chosen_move":"h1-g1","time_us":2000,"depth":null,"nodes":null,"nps":null,"score":null,"principal_variation":[]}
+"#;
+
+#[test]
+fn records_are_parsed_skipping_empty_lines() {
+    let records = parse_telemetry(RECORDS).unwrap();
+
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].room_id.as_deref(), Some("a"));
+    assert_eq!(records[0].principal_variation, vec![String::from("a1-b1")]);
+    assert_eq!(records[2].depth, None);
+}
+
+#[test]
+fn invalid_records_are_rejected() {
+    assert!(parse_telemetry("{\"turn\": 0}").is_err());
+    assert!(parse_telemetry("not json").is_err());
+}
+
+#[test]
+fn records_round_trip_through_json() {
+    let game_state = parse_position("8/8/8/3Ms*3/8/8/8/R6r 1 0 1:0").unwrap();
+    let chosen_move = parse_move("d4-e4").unwrap();
+    let search_info = SearchInfo {
+        depth: 3,
+        score: 10,
+        principal_variation: vec![chosen_move.clone()],
+        nodes: 500,
+    };
+    let record = MoveTelemetry::new(
+        Some(String::from("room")),
+        &game_state,
+        7,
+        &chosen_move,
+        Duration::from_millis(2),
+        Some(&search_info),
+    );
+
+    assert_eq!(record.nps, Some(250_000));
+    let parsed = parse_telemetry(&record.to_json().unwrap()).unwrap();
+    assert_eq!(parsed, vec![record]);
+}
+
+#[test]
+fn summary_aggregates_the_records() {
+    let records = parse_telemetry(RECORDS).unwrap();
+    let summary = TelemetrySummary::from_records(&records);
+
+    assert_eq!(
+        summary,
+        TelemetrySummary {
+            games: 2,
+            moves: 3,
+            average_time_us: Some(2000.0),
+            max_time_us: Some(3000),
+            average_legal_moves: Some(12.0),
+            average_depth: Some(5.0),
+            max_depth: Some(6),
+            average_nps: Some(2_500_000.0),
+        }
+    );
+
+    let text = summary.to_string();
+    assert!(text.contains("Games:               2"));
+    assert!(text.contains("Average time:        2.0 ms"));
+    assert!(text.contains("Maximum depth:       6"));
+}
+
+#[test]
+fn empty_summary_has_no_averages() {
+    let summary = TelemetrySummary::from_records(&[]);
+
+    assert_eq!(summary, TelemetrySummary::default());
+    assert!(summary.to_string().contains("Average depth:       -"));
+}